@@ -0,0 +1,158 @@
+//! Async morse output on embedded-hal-async
+//!
+//! Needs the `async` feature.
+
+use crate::hal::Eh1;
+use crate::keyer::{Keyer, Steps};
+use crate::{
+    CodeTable, Element, Encoded, Error, KeySink, PinSink, Prosign, ReferenceWord, Timing,
+    Unsupported, Weighting,
+};
+use eh1::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+
+/// Async morse output on a pin or another [`KeySink`]
+///
+/// Behaves exactly like the blocking [`Morse`](crate::Morse), but awaits the
+/// delays, so other tasks can run in the meantime.
+pub struct Morse<DELAY, SINK> {
+    keyer: Keyer,
+    delay: DELAY,
+    sink: SINK,
+}
+
+impl<DELAY: DelayNs, PIN: OutputPin> Morse<DELAY, PinSink<PIN, Eh1>> {
+    /// Create a new morse instance with a configurable dot_length in ms
+    /// `invert` inverts the output signal, so that the output is set low, when it's active
+    pub fn new(delay: DELAY, pin: PIN, invert: bool, dot_length: u16) -> Self {
+        Self::with_timing(delay, pin, invert, Timing::from_dot_length(dot_length))
+    }
+    /// Create a new morse instance with a `dot_length` of 300 ms
+    /// `invert` inverts the output signal, so that the output is set low, when it's active
+    pub fn new_default(delay: DELAY, pin: PIN, invert: bool) -> Self {
+        Self::new(delay, pin, invert, 300)
+    }
+    /// Create a new morse instance with a speed of `wpm` words per minute
+    /// `invert` inverts the output signal, so that the output is set low, when it's active
+    pub fn new_wpm(
+        delay: DELAY,
+        pin: PIN,
+        invert: bool,
+        wpm: u16,
+        reference: ReferenceWord,
+    ) -> Self {
+        Self::with_timing(delay, pin, invert, Timing::from_wpm(wpm, reference))
+    }
+    /// Create a new morse instance with custom timing
+    /// `invert` inverts the output signal, so that the output is set low, when it's active
+    pub fn with_timing(delay: DELAY, pin: PIN, invert: bool, timing: Timing) -> Self {
+        Self::with_sink(delay, PinSink::new(pin, invert), timing)
+    }
+}
+
+impl<DELAY: DelayNs, SINK: KeySink> Morse<DELAY, SINK> {
+    /// Create a new morse instance keying `sink`
+    pub fn with_sink(delay: DELAY, sink: SINK, timing: Timing) -> Self {
+        Self {
+            keyer: Keyer::new(timing),
+            delay,
+            sink,
+        }
+    }
+
+    /// Release the delay and the sink
+    pub fn free(self) -> (DELAY, SINK) {
+        (self.delay, self.sink)
+    }
+
+    /// Currently used timing
+    pub fn timing(&self) -> Timing {
+        self.keyer.timing()
+    }
+
+    /// Change the timing for following messages
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.keyer.set_timing(timing);
+    }
+
+    /// Change the speed to `wpm` words per minute for following messages
+    ///
+    /// Keeps the current weighting.
+    pub fn set_wpm(&mut self, wpm: u16, reference: ReferenceWord) {
+        self.keyer.set_wpm(wpm, reference);
+    }
+
+    /// Switch to Farnsworth timing for following messages, see
+    /// [`Timing::farnsworth`]
+    ///
+    /// Keeps the current weighting.
+    pub fn set_farnsworth(&mut self, char_wpm: u16, effective_wpm: u16, reference: ReferenceWord) {
+        self.keyer
+            .set_farnsworth(char_wpm, effective_wpm, reference);
+    }
+
+    /// Change the weighting for following messages, see [`Weighting`]
+    pub fn set_weighting(&mut self, weighting: Weighting) {
+        self.keyer.set_weighting(weighting);
+    }
+
+    /// Effective speed in words per minute
+    pub fn wpm(&self, reference: ReferenceWord) -> u16 {
+        self.keyer.timing().wpm(reference)
+    }
+
+    /// Encode following messages with `table`, e.g. one of
+    /// [`tables`](crate::tables)
+    pub fn set_table(&mut self, table: &'static dyn CodeTable) {
+        self.keyer.set_table(table);
+    }
+
+    /// Change how characters without a morse representation are handled,
+    /// see [`Unsupported`]
+    pub fn set_unsupported(&mut self, policy: Unsupported) {
+        self.keyer.set_unsupported(policy);
+    }
+
+    /// Output a string as a morse message, see
+    /// [`Morse::output_str`](crate::Morse::output_str)
+    pub async fn output_str(&mut self, output: &str) -> Result<(), Error<SINK::Error>> {
+        let steps = self.keyer.message(output)?;
+        key(&mut self.delay, &mut self.sink, steps).await
+    }
+
+    /// Output a single prosign
+    pub async fn output_prosign(&mut self, prosign: Prosign) -> Result<(), Error<SINK::Error>> {
+        key(
+            &mut self.delay,
+            &mut self.sink,
+            self.keyer.steps(prosign.elements()),
+        )
+        .await
+    }
+
+    /// Output a message encoded at compile time, see
+    /// [`morse!`](crate::morse!)
+    pub async fn output_encoded<const N: usize>(
+        &mut self,
+        encoded: &Encoded<N>,
+    ) -> Result<(), Error<SINK::Error>> {
+        key(
+            &mut self.delay,
+            &mut self.sink,
+            self.keyer.steps(encoded.elements()),
+        )
+        .await
+    }
+}
+
+/// Key `sink` for all `steps`, awaiting each
+async fn key<DELAY: DelayNs, SINK: KeySink>(
+    delay: &mut DELAY,
+    sink: &mut SINK,
+    mut steps: Steps<'_, impl Iterator<Item = Element>>,
+) -> Result<(), Error<SINK::Error>> {
+    while let Some(duration) = steps.key(sink).map_err(Error::Output)? {
+        delay.delay_ms(duration.into()).await;
+    }
+    Ok(())
+}
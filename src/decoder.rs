@@ -0,0 +1,218 @@
+use crate::{ReferenceWord, Timing, CHARS, CHARS_START};
+
+/// Number of nodes in a complete binary tree for characters of up to 7
+/// elements
+const TREE_SIZE: usize = 255;
+
+/// Position in the tree of a pattern that doesn't match any character
+const INVALID: usize = usize::MAX;
+
+/// `CHARS` as a binary tree, with the empty pattern at the root
+///
+/// The children of node `n` are `2n + 1` for a dot and `2n + 2` for a dash.
+/// Nodes hold the ASCII value of their character, or 0.
+const TREE: [u8; TREE_SIZE] = build_tree();
+
+const fn build_tree() -> [u8; TREE_SIZE] {
+    let mut tree = [0; TREE_SIZE];
+    let mut i = 0;
+    while i < CHARS.len() {
+        let morse_char = CHARS[i];
+        if morse_char.len() != 0 {
+            let mut node = 0;
+            let mut rest = morse_char;
+            while rest.len() != 0 {
+                let (mark, next) = rest.pop();
+                node = 2 * node + 1 + mark as usize;
+                rest = next;
+            }
+            tree[node] = CHARS_START as u8 + i as u8;
+        }
+        i += 1;
+    }
+    tree
+}
+
+/// Decodes morse from the durations of marks and spaces
+///
+/// Marks shorter than two dots are dots, longer ones dashes. Spaces shorter
+/// than two dots separate elements, up to five dots characters and longer
+/// ones words. Marks and spaces are expected to alternate.
+///
+/// Patterns without a matching character decode to
+/// [`char::REPLACEMENT_CHARACTER`].
+///
+/// An adaptive decoder (see [`Decoder::adaptive`]) follows changes in the
+/// sender's speed, by updating the dot length with every mark and element
+/// gap.
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    /// Dot length in 1/16 ms, so the estimate can move in small steps
+    dot_length: u32,
+    adaptive: bool,
+    /// Position in `TREE` of the current character, 0 if none is started
+    node: usize,
+    space_pending: bool,
+}
+
+impl Decoder {
+    /// Create a decoder for a fixed `dot_length` in ms
+    pub fn new(dot_length: u16) -> Self {
+        Self {
+            dot_length: u32::from(dot_length) * 16,
+            adaptive: false,
+            node: 0,
+            space_pending: false,
+        }
+    }
+
+    /// Create a decoder tracking the sender's speed, starting with an
+    /// estimate of `dot_length` ms
+    ///
+    /// The estimate should be within a factor of two of the actual dot
+    /// length, otherwise the first few characters may be decoded wrongly.
+    pub fn adaptive(dot_length: u16) -> Self {
+        Self {
+            adaptive: true,
+            ..Self::new(dot_length)
+        }
+    }
+
+    /// Dot length in ms the decoder expects
+    ///
+    /// For an adaptive decoder, this is the current estimate.
+    pub fn dot_length(&self) -> u16 {
+        ((self.dot_length + 8) / 16) as u16
+    }
+
+    /// Current speed estimate in words per minute, of the characters
+    /// themselves
+    pub fn wpm(&self, reference: ReferenceWord) -> u16 {
+        Timing::from_dot_length(self.dot_length()).wpm(reference)
+    }
+
+    /// Process a mark (`mark == true`) or space lasting `duration` ms
+    ///
+    /// Returns a character once it's complete, i.e. at the space following
+    /// it. Word gaps are returned as `' '` at the start of the next mark.
+    pub fn push(&mut self, mark: bool, duration: u16) -> Option<char> {
+        let dot = self.dot_length;
+        let duration = u32::from(duration) * 16;
+        if mark {
+            let dash = duration >= 2 * dot;
+            self.adapt(if dash { duration / 3 } else { duration });
+            if self.node != INVALID {
+                self.node = 2 * self.node + 1 + dash as usize;
+                if self.node >= TREE_SIZE {
+                    self.node = INVALID;
+                }
+            }
+            if self.space_pending {
+                self.space_pending = false;
+                return Some(' ');
+            }
+            None
+        } else if duration < 2 * dot {
+            self.adapt(duration);
+            None
+        } else {
+            let c = self.finish();
+            self.space_pending |= c.is_some() && duration >= 5 * dot;
+            c
+        }
+    }
+
+    /// Move the dot length estimate towards `dot_length` (in 1/16 ms)
+    fn adapt(&mut self, dot_length: u32) {
+        if self.adaptive {
+            let estimate = (3 * self.dot_length + dot_length) / 4;
+            self.dot_length = estimate.clamp(16, u32::from(u16::MAX) * 16);
+        }
+    }
+
+    /// End the current character, e.g. at the end of a transmission
+    pub fn finish(&mut self) -> Option<char> {
+        let node = core::mem::replace(&mut self.node, 0);
+        match node {
+            0 => None,
+            INVALID => Some(char::REPLACEMENT_CHARACTER),
+            node => match TREE[node] {
+                0 => Some(char::REPLACEMENT_CHARACTER),
+                c => Some(c as char),
+            },
+        }
+    }
+}
+
+/// Turns a sampled signal into mark and space durations for a [`Decoder`]
+///
+/// Shared by [`PinDecoder`](crate::PinDecoder) and
+/// [`ToneDetector`](crate::ToneDetector). Durations are counted in units of
+/// `1 / rate` s, e.g. ms or samples.
+#[derive(Debug, Clone)]
+pub(crate) struct Levels {
+    decoder: Decoder,
+    rate: u32,
+    /// Current level, `true` during marks
+    mark: bool,
+    /// Duration of the current level
+    duration: u32,
+    /// Whether the current space was already passed to the decoder
+    flushed: bool,
+}
+
+impl Levels {
+    pub(crate) fn new(decoder: Decoder, rate: u32) -> Self {
+        Self {
+            decoder,
+            rate,
+            mark: false,
+            duration: 0,
+            flushed: true,
+        }
+    }
+
+    pub(crate) fn decoder(&self) -> &Decoder {
+        &self.decoder
+    }
+
+    /// Current level, `true` during marks
+    pub(crate) fn mark(&self) -> bool {
+        self.mark
+    }
+
+    /// The current level lasted `elapsed` units longer
+    ///
+    /// Returns the last character of a transmission, once the following
+    /// space is as long as a word gap.
+    pub(crate) fn extend(&mut self, elapsed: u32) -> Option<char> {
+        self.duration = self.duration.saturating_add(elapsed);
+        if !self.mark && !self.flushed {
+            // Finish the character without waiting for the next mark
+            let word_gap = u32::from(self.decoder.dot_length()) * 7;
+            if u32::from(self.ms()) >= word_gap {
+                self.flushed = true;
+                return self.decoder.push(false, self.ms());
+            }
+        }
+        None
+    }
+
+    /// The level changed to `mark`, `elapsed` units ago
+    pub(crate) fn change(&mut self, mark: bool, elapsed: u32) -> Option<char> {
+        let (was_mark, duration) = (self.mark, self.ms());
+        self.mark = mark;
+        self.duration = elapsed;
+        if core::mem::replace(&mut self.flushed, false) {
+            // Space before the first mark, or already passed on
+            return None;
+        }
+        self.decoder.push(was_mark, duration)
+    }
+
+    /// Duration of the current level in ms, saturated
+    fn ms(&self) -> u16 {
+        let ms = u64::from(self.duration) * 1000 / u64::from(self.rate);
+        ms.min(u64::from(u16::MAX)) as u16
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{Element, MorseChar, CHARS, CHARS_START, UNSUPPORTED};
+
+/// Gaps before a mark, stored in the upper half of its nibble
+const ELEMENT_GAP: u8 = 0;
+const INTERNAL_GAP: u8 = 1;
+const CHAR_GAP: u8 = 2;
+const WORD_GAP: u8 = 3;
+
+/// A message encoded at compile time, see [`morse!`](crate::morse!)
+///
+/// Stores every mark in 4 bits, together with the gap before it, so `N` is
+/// about half the number of marks (see [`encoded_len`]). Follows the same
+/// rules as [`encode`](crate::encode) with the international table, but only
+/// supports ASCII. Unsupported characters and unmatched angle brackets
+/// aren't skipped, they panic and are compile errors in constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Encoded<const N: usize> {
+    nibbles: [u8; N],
+    marks: usize,
+}
+
+impl<const N: usize> Encoded<N> {
+    /// Encode `message`
+    ///
+    /// Panics if `N` isn't [`encoded_len(message)`](encoded_len) or the
+    /// message contains unsupported characters.
+    pub const fn new(message: &str) -> Self {
+        if N != encoded_len(message) {
+            panic!("wrong length for encoded morse message");
+        }
+        let mut nibbles = [0; N];
+        let mut marks = 0;
+        let mut encoder = Encoder::new(message);
+        while let Some((next, nibble)) = encoder.next() {
+            nibbles[marks / 2] |= nibble << (marks % 2 * 4);
+            marks += 1;
+            encoder = next;
+        }
+        Self { nibbles, marks }
+    }
+
+    /// The elements of the message, like [`encode`](crate::encode)
+    pub fn elements(&self) -> EncodedElements<'_> {
+        EncodedElements {
+            nibbles: &self.nibbles[..],
+            marks: self.marks,
+            index: 0,
+            gap_sent: false,
+        }
+    }
+}
+
+/// Number of bytes needed to encode `message`, see [`Encoded`]
+///
+/// Panics if the message contains unsupported characters.
+pub const fn encoded_len(message: &str) -> usize {
+    let mut marks: usize = 0;
+    let mut encoder = Encoder::new(message);
+    while let Some((next, _)) = encoder.next() {
+        marks += 1;
+        encoder = next;
+    }
+    marks.div_ceil(2)
+}
+
+/// Iterator over the elements of an [`Encoded`] message
+#[derive(Debug, Clone)]
+pub struct EncodedElements<'a> {
+    nibbles: &'a [u8],
+    marks: usize,
+    index: usize,
+    /// Whether the gap before the next mark was already yielded
+    gap_sent: bool,
+}
+
+impl Iterator for EncodedElements<'_> {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        if self.index == self.marks {
+            return None;
+        }
+        let nibble = self.nibbles[self.index / 2] >> (self.index % 2 * 4);
+        if self.index != 0 && !self.gap_sent {
+            self.gap_sent = true;
+            return Some(match (nibble >> 2) & 0b11 {
+                ELEMENT_GAP => Element::ElementGap,
+                INTERNAL_GAP => Element::InternalGap,
+                CHAR_GAP => Element::CharGap,
+                _ => Element::WordGap,
+            });
+        }
+        self.gap_sent = false;
+        self.index += 1;
+        Some(match nibble & 0b11 {
+            0 => Element::Dot,
+            1 => Element::Dash,
+            2 => Element::LongDash,
+            _ => Element::ExtraLongDash,
+        })
+    }
+}
+
+/// Walks a message at compile time, yielding the nibble of every mark
+#[derive(Clone, Copy)]
+struct Encoder<'a> {
+    message: &'a [u8],
+    index: usize,
+    /// Index of the closing bracket of the current prosign
+    run_end: Option<usize>,
+    morse_char: MorseChar,
+    /// Gap before the next mark, `None` before the first one
+    gap: Option<u8>,
+}
+
+impl<'a> Encoder<'a> {
+    const fn new(message: &'a str) -> Self {
+        Self {
+            message: message.as_bytes(),
+            index: 0,
+            run_end: None,
+            morse_char: UNSUPPORTED,
+            gap: None,
+        }
+    }
+
+    const fn next(mut self) -> Option<(Self, u8)> {
+        while self.morse_char.len() == 0 {
+            if let Some(end) = self.run_end {
+                if self.index == end {
+                    self.run_end = None;
+                    self.index += 1;
+                    // Only a prosign that sent anything ends a character
+                    if let Some(ELEMENT_GAP) = self.gap {
+                        self.gap = Some(CHAR_GAP);
+                    }
+                    continue;
+                }
+            }
+            if self.index == self.message.len() {
+                return None;
+            }
+            let c = self.message[self.index];
+            self.index += 1;
+            if c == b'<' && self.run_end.is_none() {
+                if let Some(end) = self.find_end() {
+                    self.run_end = Some(end);
+                    continue;
+                }
+            }
+            if is_whitespace(c) {
+                if self.run_end.is_none() && self.gap.is_some() {
+                    self.gap = Some(WORD_GAP);
+                }
+                continue;
+            }
+            self.morse_char = lookup(c);
+        }
+        let gap = match self.gap {
+            Some(gap) => gap,
+            None => ELEMENT_GAP,
+        };
+        let (mark, rest) = self.morse_char.pop();
+        let nibble = mark | gap << 2;
+        self.morse_char = rest;
+        self.gap = Some(if rest.len() == 0 && self.run_end.is_none() {
+            CHAR_GAP
+        } else if rest.len() != 0 && rest.is_spaced() {
+            INTERNAL_GAP
+        } else {
+            ELEMENT_GAP
+        });
+        Some((self, nibble))
+    }
+
+    /// Index of the next `>`
+    const fn find_end(&self) -> Option<usize> {
+        let mut index = self.index;
+        while index < self.message.len() {
+            if self.message[index] == b'>' {
+                return Some(index);
+            }
+            index += 1;
+        }
+        None
+    }
+}
+
+/// Whether an ASCII character is whitespace, like [`char::is_whitespace`]
+///
+/// Unlike [`u8::is_ascii_whitespace`], this includes the vertical tab.
+const fn is_whitespace(c: u8) -> bool {
+    matches!(c, b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r')
+}
+
+/// Look up an ASCII character in the international table
+const fn lookup(c: u8) -> MorseChar {
+    let c = c.to_ascii_uppercase();
+    let start = CHARS_START as u8;
+    if c >= start && ((c - start) as usize) < CHARS.len() {
+        let morse_char = CHARS[(c - start) as usize];
+        if morse_char.length != 0 {
+            return morse_char;
+        }
+    }
+    panic!("unsupported character in morse message");
+}
+
+/// Encode a message at compile time, for playback with
+/// [`Morse::output_encoded`](crate::Morse::output_encoded)
+///
+/// Expands to a constant [`Encoded`] message, so unsupported characters are
+/// compile errors and no table lookups are needed at runtime.
+///
+/// ```
+/// use embedded_morse::{morse, Encoded};
+///
+/// static BEACON: Encoded<11> = morse!("VVV DE <KA>");
+/// ```
+///
+/// ```compile_fail
+/// let beacon = embedded_morse::morse!("100%");
+/// ```
+#[macro_export]
+macro_rules! morse {
+    ($message:expr) => {{
+        const ENCODED: $crate::Encoded<{ $crate::encoded_len($message) }> =
+            $crate::Encoded::new($message);
+        ENCODED
+    }};
+}
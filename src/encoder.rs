@@ -0,0 +1,329 @@
+use crate::{CodeTable, Error, International, MorseChar, Prosign, Timing, UNSUPPORTED};
+use core::str::Chars;
+
+/// Part of a morse message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
+pub enum Element {
+    /// Short mark, one unit
+    Dot,
+    /// Long mark, three units
+    Dash,
+    /// Long dash of American Morse (`L`), a dash and two units
+    LongDash,
+    /// Extra long dash of American Morse (`0`), a dash and three units
+    ExtraLongDash,
+    /// Gap between the marks of a character, one unit
+    ElementGap,
+    /// Space within a character of American Morse, two units
+    InternalGap,
+    /// Gap between characters, three units
+    CharGap,
+    /// Gap between words, seven units
+    WordGap,
+}
+
+impl Element {
+    /// Whether the key is down during this element
+    pub fn is_mark(self) -> bool {
+        matches!(
+            self,
+            Element::Dot | Element::Dash | Element::LongDash | Element::ExtraLongDash
+        )
+    }
+
+    /// Duration of this element in ms
+    pub fn duration(self, timing: &Timing) -> u16 {
+        match self {
+            Element::Dot => timing.dot(),
+            Element::Dash => timing.dash(),
+            Element::LongDash => timing.long_dash(),
+            Element::ExtraLongDash => timing.extra_long_dash(),
+            Element::ElementGap => timing.element_gap(),
+            Element::InternalGap => timing.internal_gap(),
+            Element::CharGap => timing.char_gap(),
+            Element::WordGap => timing.word_gap(),
+        }
+    }
+}
+
+/// What to do with characters without a morse representation
+///
+/// Whitespace always separates words and is never unsupported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unsupported {
+    /// Leave them out, the default
+    #[default]
+    Skip,
+    /// Send this character instead, e.g. `'?'`
+    ///
+    /// Skips them, if the substitute isn't supported either.
+    Substitute(char),
+    /// Send this prosign instead, e.g. [`Prosign::Error`]
+    SubstituteProsign(Prosign),
+    /// Don't send anything and return [`Error::Unsupported`] for the first
+    /// one
+    ///
+    /// Only outputs check the whole message up front, [`Elements`] skip them.
+    Fail,
+}
+
+/// Result of looking up a character
+enum Lookup {
+    Code(MorseChar),
+    Expand(&'static str),
+    Skip,
+}
+
+/// Look up `c`, falling back to its substitute
+fn lookup(table: &dyn CodeTable, policy: Unsupported, c: char) -> Lookup {
+    if let Some(morse_char) = table.code(c) {
+        return Lookup::Code(morse_char);
+    }
+    if let Some(expansion) = table.expand(c) {
+        return Lookup::Expand(expansion);
+    }
+    let substitute = match policy {
+        _ if c.is_whitespace() => None,
+        Unsupported::Substitute(substitute) => table.code(substitute),
+        Unsupported::SubstituteProsign(prosign) => Some(prosign.morse_char()),
+        Unsupported::Skip | Unsupported::Fail => None,
+    };
+    substitute.map_or(Lookup::Skip, Lookup::Code)
+}
+
+/// Look up `c` together with the next character, consuming that one if they
+/// form a digraph
+fn digraph(table: &dyn CodeTable, c: char, chars: &mut Chars) -> Option<MorseChar> {
+    let mut rest = chars.clone();
+    let morse_char = table.digraph(c, rest.next()?)?;
+    *chars = rest;
+    Some(morse_char)
+}
+
+/// Find the first character of a message without a morse representation in
+/// the international table, see [`Elements::find_unsupported`]
+pub fn find_unsupported(message: &str) -> Option<(usize, char)> {
+    Elements::new(message).find_unsupported()
+}
+
+/// Split a message into its elements, without any output
+///
+/// Follows the same rules as [`Morse::output_str`](crate::Morse::output_str),
+/// see [`Timing`]. Gaps are only yielded between marks, so the iterator
+/// starts with a mark and ends with one.
+pub fn encode(message: &str) -> Elements<'_> {
+    Elements::new(message)
+}
+
+/// Iterator over the elements of a message, see [`encode`]
+#[derive(Debug, Clone)]
+pub struct Elements<'a> {
+    message: &'a str,
+    chars: Chars<'a>,
+    /// Remaining characters of a prosign
+    run: Option<Chars<'a>>,
+    /// Remaining characters sent in place of a single one
+    expansion: Option<Chars<'static>>,
+    /// Remaining marks of the current character
+    morse_char: MorseChar,
+    /// Gap before the next mark, `None` before the first one
+    gap: Option<Element>,
+    table: &'static dyn CodeTable,
+    unsupported: Unsupported,
+}
+
+impl<'a> Elements<'a> {
+    pub(crate) fn new(message: &'a str) -> Self {
+        Self {
+            message,
+            chars: message.chars(),
+            run: None,
+            expansion: None,
+            morse_char: UNSUPPORTED,
+            gap: None,
+            table: &International,
+            unsupported: Unsupported::Skip,
+        }
+    }
+
+    /// Encode characters with `table` instead of the international one
+    pub fn with_table(self, table: &'static dyn CodeTable) -> Self {
+        Self { table, ..self }
+    }
+
+    /// Handle characters without a morse representation according to
+    /// `policy`, instead of skipping them
+    pub fn with_unsupported(self, policy: Unsupported) -> Self {
+        Self {
+            unsupported: policy,
+            ..self
+        }
+    }
+
+    /// Find the first character of the message without a morse
+    /// representation in the table
+    ///
+    /// Returns its byte index and the character. Angle brackets of prosigns
+    /// and whitespace are supported, unmatched angle brackets aren't.
+    pub fn find_unsupported(&self) -> Option<(usize, char)> {
+        let table = self.table;
+        let supported = |c| {
+            table.code(c).is_some()
+                || table
+                    .expand(c)
+                    .is_some_and(|expansion| expansion.chars().all(|c| table.code(c).is_some()))
+        };
+        let mut prosign = false;
+        for (index, c) in self.message.char_indices() {
+            match c {
+                '<' if !prosign && self.message[index..].contains('>') => prosign = true,
+                '>' if prosign => prosign = false,
+                c if c.is_whitespace() || supported(c) => {}
+                c => return Some((index, c)),
+            }
+        }
+        None
+    }
+
+    /// Fail on the first unsupported character, if the policy is
+    /// [`Unsupported::Fail`]
+    pub(crate) fn check<E>(&self) -> Result<(), Error<E>> {
+        if self.unsupported != Unsupported::Fail {
+            return Ok(());
+        }
+        match self.find_unsupported() {
+            Some((index, character)) => Err(Error::Unsupported { index, character }),
+            None => Ok(()),
+        }
+    }
+
+    /// Elements of a single character
+    pub(crate) fn from_morse_char(morse_char: MorseChar) -> Self {
+        let mut elements = Self::new("");
+        elements.load(morse_char);
+        elements
+    }
+
+    /// Pair each element with its duration in ms, as `(mark, duration)`
+    pub fn timed(self, timing: Timing) -> Timed<'a> {
+        Timed {
+            elements: self,
+            timing,
+        }
+    }
+
+    /// Load the next character, returns `false` at the end of the message
+    fn next_char(&mut self) -> bool {
+        let (table, policy) = (self.table, self.unsupported);
+        loop {
+            if let Some(expansion) = &mut self.expansion {
+                if let Some(morse_char) = expansion.find_map(|c| table.code(c)) {
+                    self.load(morse_char);
+                    return true;
+                }
+                self.expansion = None;
+            }
+            if let Some(run) = &mut self.run {
+                match run.next() {
+                    Some(c) => match digraph(table, c, run)
+                        .map_or_else(|| lookup(table, policy, c), Lookup::Code)
+                    {
+                        Lookup::Code(morse_char) => {
+                            self.load(morse_char);
+                            return true;
+                        }
+                        Lookup::Expand(expansion) => self.expansion = Some(expansion.chars()),
+                        Lookup::Skip => {}
+                    },
+                    None => {
+                        self.run = None;
+                        // Only a prosign that sent anything ends a character
+                        if self.gap == Some(Element::ElementGap) {
+                            self.gap = Some(Element::CharGap);
+                        }
+                    }
+                }
+                continue;
+            }
+            let c = match self.chars.next() {
+                Some(c) => c,
+                None => return false,
+            };
+            if c == '<' {
+                let rest = self.chars.as_str();
+                if let Some(end) = rest.find('>') {
+                    self.run = Some(rest[..end].chars());
+                    self.chars = rest[end + 1..].chars();
+                    continue;
+                }
+            }
+            if let Some(morse_char) = digraph(table, c, &mut self.chars) {
+                self.load(morse_char);
+                return true;
+            }
+            match lookup(table, policy, c) {
+                Lookup::Code(morse_char) => {
+                    self.load(morse_char);
+                    return true;
+                }
+                Lookup::Expand(expansion) => self.expansion = Some(expansion.chars()),
+                Lookup::Skip if c.is_whitespace() && self.gap.is_some() => {
+                    self.gap = Some(Element::WordGap);
+                }
+                Lookup::Skip => {}
+            }
+        }
+    }
+
+    fn load(&mut self, morse_char: MorseChar) {
+        self.morse_char = morse_char;
+    }
+}
+
+impl Iterator for Elements<'_> {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        if self.morse_char.len() == 0 && !self.next_char() {
+            return None;
+        }
+        if let Some(gap) = self.gap.take() {
+            return Some(gap);
+        }
+        let (mark, rest) = self.morse_char.pop();
+        let element = match mark {
+            0 => Element::Dot,
+            1 => Element::Dash,
+            2 => Element::LongDash,
+            _ => Element::ExtraLongDash,
+        };
+        self.morse_char = rest;
+        self.gap = Some(if rest.len() == 0 && self.run.is_none() {
+            Element::CharGap
+        } else if rest.len() != 0 && rest.is_spaced() {
+            Element::InternalGap
+        } else {
+            Element::ElementGap
+        });
+        Some(element)
+    }
+}
+
+/// Iterator over `(mark, duration in ms)` pairs, see [`Elements::timed`]
+///
+/// `mark` is `true` while the key is down.
+#[derive(Debug, Clone)]
+pub struct Timed<'a> {
+    elements: Elements<'a>,
+    timing: Timing,
+}
+
+impl Iterator for Timed<'_> {
+    type Item = (bool, u16);
+
+    fn next(&mut self) -> Option<(bool, u16)> {
+        let element = self.elements.next()?;
+        Some((element.is_mark(), element.duration(&self.timing)))
+    }
+}
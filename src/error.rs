@@ -0,0 +1,28 @@
+use core::fmt;
+
+/// Error of the morse output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<E> {
+    /// Error of the pin or other output
+    Output(E),
+    /// Character without a morse representation, with
+    /// [`Unsupported::Fail`](crate::Unsupported::Fail)
+    Unsupported {
+        /// Byte index in the message
+        index: usize,
+        character: char,
+    },
+}
+
+impl<E: fmt::Display> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Output(error) => write!(f, "output error: {}", error),
+            Error::Unsupported { index, character } => write!(
+                f,
+                "unsupported character {:?} at index {}",
+                character, index
+            ),
+        }
+    }
+}
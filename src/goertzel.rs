@@ -0,0 +1,105 @@
+use crate::decoder::Levels;
+use crate::trig;
+use crate::Decoder;
+
+/// Fixed-point Goertzel filter, measuring a single frequency in a block of
+/// samples
+#[derive(Debug, Clone)]
+pub struct Goertzel {
+    /// 2 * cos(2 * pi * frequency / sample_rate), in Q14
+    coeff: i64,
+}
+
+impl Goertzel {
+    /// Create a filter for `frequency` in Hz, which has to be below half the
+    /// `sample_rate`
+    pub fn new(sample_rate: u32, frequency: u32) -> Self {
+        // 2 * cos in Q14 is the same as cos in Q15
+        let coeff = trig::cos(trig::phase_step(frequency, sample_rate));
+        Self {
+            coeff: i64::from(coeff),
+        }
+    }
+
+    /// Amplitude of the frequency in `samples`, in the same unit as the
+    /// samples
+    ///
+    /// A sine at the filter's frequency with an amplitude of 10000 results
+    /// in about 10000. The frequency resolution is about
+    /// `sample_rate / samples.len()`, so blocks have to be long enough to
+    /// separate the tone from others.
+    pub fn magnitude(&self, samples: &[i16]) -> u16 {
+        if samples.is_empty() {
+            return 0;
+        }
+        let (mut s1, mut s2) = (0i64, 0i64);
+        for &sample in samples {
+            let s0 = i64::from(sample) + ((self.coeff * s1) >> 14) - s2;
+            s2 = s1;
+            s1 = s0;
+        }
+        let power = s1 * s1 + s2 * s2 - ((self.coeff * s1) >> 14) * s2;
+        let magnitude = 2 * trig::sqrt(power.max(0) as u64) / samples.len() as u64;
+        magnitude.min(u64::from(u16::MAX)) as u16
+    }
+}
+
+/// Decodes morse from audio, e.g. the output of a receiver sampled by an ADC
+///
+/// Each block of samples is checked for the tone with a [`Goertzel`] filter.
+/// A tone is detected once its magnitude reaches the `on` threshold and
+/// lasts until it drops below the `off` threshold. The resulting mark and
+/// space durations are fed into a [`Decoder`].
+///
+/// The block length determines the time resolution, it should be well below
+/// a dot length, e.g. 5-10 ms.
+pub struct ToneDetector {
+    goertzel: Goertzel,
+    on: u16,
+    off: u16,
+    /// Whether the tone is present, with durations in samples
+    levels: Levels,
+}
+
+impl ToneDetector {
+    /// Create a detector for a tone of `frequency` Hz, with `on` and `off`
+    /// magnitude thresholds (see [`Goertzel::magnitude`])
+    pub fn new(sample_rate: u32, frequency: u32, on: u16, off: u16, decoder: Decoder) -> Self {
+        Self {
+            goertzel: Goertzel::new(sample_rate, frequency),
+            on,
+            off,
+            levels: Levels::new(decoder, sample_rate),
+        }
+    }
+
+    /// The decoder fed by this detector, e.g. for its speed estimate
+    pub fn decoder(&self) -> &Decoder {
+        self.levels.decoder()
+    }
+
+    /// Whether the tone is currently present
+    pub fn tone(&self) -> bool {
+        self.levels.mark()
+    }
+
+    /// Process the next block of samples
+    ///
+    /// Returns a character once it's complete. The last character of a
+    /// transmission is returned once the following space is as long as a
+    /// word gap.
+    pub fn process(&mut self, block: &[i16]) -> Option<char> {
+        let magnitude = self.goertzel.magnitude(block);
+        let tone = if self.levels.mark() {
+            magnitude >= self.off
+        } else {
+            magnitude >= self.on
+        };
+        let samples = block.len() as u32;
+        if tone == self.levels.mark() {
+            self.levels.extend(samples)
+        } else {
+            self.levels.change(tone, samples)
+        }
+    }
+}
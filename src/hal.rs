@@ -0,0 +1,138 @@
+//! Support for multiple embedded-hal generations
+//!
+//! The `eh0` feature (enabled by default) supports embedded-hal 0.2, the
+//! `eh1` feature embedded-hal 1.0. Both can be enabled at the same time.
+//!
+//! The traits in this module are implemented for every type implementing
+//! the corresponding embedded-hal trait, so HAL types can be passed directly.
+//! They are generic over a marker type for the generation, which is normally
+//! inferred. Only if a type implements the traits of both generations, the
+//! marker has to be specified, e.g. `morse.output_str::<Eh1>("SOS")` or
+//! `PinSink::<_, Eh1>::new(pin, false)`.
+
+/// Marker for embedded-hal 0.2
+#[cfg(feature = "eh0")]
+pub enum Eh0 {}
+
+/// Marker for embedded-hal 1.0
+#[cfg(feature = "eh1")]
+pub enum Eh1 {}
+
+/// Blocking delay in ms
+pub trait Delay<HAL> {
+    fn delay_ms(&mut self, ms: u16);
+}
+
+/// Digital output pin
+pub trait OutputPin<HAL> {
+    type Error;
+
+    fn set_low(&mut self) -> Result<(), Self::Error>;
+    fn set_high(&mut self) -> Result<(), Self::Error>;
+}
+
+/// PWM channel
+pub trait Pwm<HAL> {
+    type Error;
+
+    fn max_duty(&mut self) -> u16;
+    /// Set the duty cycle, turning the output off at 0
+    fn set_duty(&mut self, duty: u16) -> Result<(), Self::Error>;
+}
+
+/// Digital input pin
+pub trait InputPin<HAL> {
+    type Error;
+
+    fn is_high(&mut self) -> Result<bool, Self::Error>;
+}
+
+#[cfg(feature = "eh0")]
+impl<T: eh0::blocking::delay::DelayMs<u16>> Delay<Eh0> for T {
+    fn delay_ms(&mut self, ms: u16) {
+        eh0::blocking::delay::DelayMs::delay_ms(self, ms)
+    }
+}
+
+#[cfg(feature = "eh0")]
+impl<T: eh0::digital::v2::OutputPin> OutputPin<Eh0> for T {
+    type Error = T::Error;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        eh0::digital::v2::OutputPin::set_low(self)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        eh0::digital::v2::OutputPin::set_high(self)
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<T: eh1::delay::DelayNs> Delay<Eh1> for T {
+    fn delay_ms(&mut self, ms: u16) {
+        eh1::delay::DelayNs::delay_ms(self, ms.into())
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<T: eh1::digital::OutputPin> OutputPin<Eh1> for T {
+    type Error = T::Error;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        eh1::digital::OutputPin::set_low(self)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        eh1::digital::OutputPin::set_high(self)
+    }
+}
+
+#[cfg(feature = "eh0")]
+impl<T: eh0::digital::v2::InputPin> InputPin<Eh0> for T {
+    type Error = T::Error;
+
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        eh0::digital::v2::InputPin::is_high(self)
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<T: eh1::digital::InputPin> InputPin<Eh1> for T {
+    type Error = T::Error;
+
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        eh1::digital::InputPin::is_high(self)
+    }
+}
+
+#[cfg(feature = "eh0")]
+impl<T: eh0::PwmPin<Duty = u16>> Pwm<Eh0> for T {
+    type Error = core::convert::Infallible;
+
+    fn max_duty(&mut self) -> u16 {
+        eh0::PwmPin::get_max_duty(self)
+    }
+
+    fn set_duty(&mut self, duty: u16) -> Result<(), Self::Error> {
+        if duty == 0 {
+            eh0::PwmPin::disable(self);
+        } else {
+            eh0::PwmPin::set_duty(self, duty);
+            eh0::PwmPin::enable(self);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<T: eh1::pwm::SetDutyCycle> Pwm<Eh1> for T {
+    type Error = T::Error;
+
+    fn max_duty(&mut self) -> u16 {
+        eh1::pwm::SetDutyCycle::max_duty_cycle(self)
+    }
+
+    fn set_duty(&mut self, duty: u16) -> Result<(), Self::Error> {
+        eh1::pwm::SetDutyCycle::set_duty_cycle(self, duty)
+    }
+}
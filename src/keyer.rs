@@ -0,0 +1,147 @@
+use crate::{
+    CodeTable, Element, Elements, Error, International, KeySink, ReferenceWord, Timing,
+    Unsupported, Weighting,
+};
+use core::iter::Peekable;
+
+/// Settings and state shared by [`Morse`](crate::Morse) and `asynch::Morse`
+///
+/// Turns messages into the [`Steps`] to key, the output types only wait for
+/// them.
+pub(crate) struct Keyer {
+    timing: Timing,
+    table: &'static dyn CodeTable,
+    unsupported: Unsupported,
+    /// Gap before the next mark, `None` before the first one
+    gap: Option<Element>,
+}
+
+impl Keyer {
+    pub(crate) fn new(timing: Timing) -> Self {
+        Self {
+            timing,
+            table: &International,
+            unsupported: Unsupported::Skip,
+            gap: None,
+        }
+    }
+
+    pub(crate) fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    pub(crate) fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
+    /// Keeps the current weighting
+    pub(crate) fn set_wpm(&mut self, wpm: u16, reference: ReferenceWord) {
+        self.timing = Timing::from_wpm(wpm, reference).with_weighting(self.timing.weighting());
+    }
+
+    /// Keeps the current weighting
+    pub(crate) fn set_farnsworth(
+        &mut self,
+        char_wpm: u16,
+        effective_wpm: u16,
+        reference: ReferenceWord,
+    ) {
+        self.timing = Timing::farnsworth(char_wpm, effective_wpm, reference)
+            .with_weighting(self.timing.weighting());
+    }
+
+    pub(crate) fn set_weighting(&mut self, weighting: Weighting) {
+        self.timing = self.timing.with_weighting(weighting);
+    }
+
+    pub(crate) fn set_table(&mut self, table: &'static dyn CodeTable) {
+        self.table = table;
+    }
+
+    pub(crate) fn set_unsupported(&mut self, policy: Unsupported) {
+        self.unsupported = policy;
+    }
+
+    /// Steps of a message, checked according to the unsupported policy
+    pub(crate) fn message<'k, 'a, E>(
+        &'k mut self,
+        message: &'a str,
+    ) -> Result<Steps<'k, Elements<'a>>, Error<E>> {
+        let elements = Elements::new(message)
+            .with_table(self.table)
+            .with_unsupported(self.unsupported);
+        elements.check()?;
+        // Whitespace at either end separates the message from its neighbours
+        if message.starts_with(char::is_whitespace) {
+            self.separate_word();
+        }
+        let mut steps = self.steps(elements);
+        steps.word_gap = message.ends_with(char::is_whitespace);
+        Ok(steps)
+    }
+
+    pub(crate) fn steps<I: Iterator<Item = Element>>(&mut self, elements: I) -> Steps<'_, I> {
+        let mut elements = elements.peekable();
+        // The previous message ended right after its last mark
+        let gap = match elements.peek() {
+            Some(_) => self.gap,
+            None => None,
+        };
+        Steps {
+            keyer: self,
+            elements,
+            gap,
+            keyed: false,
+            word_gap: false,
+        }
+    }
+
+    /// Separate the next mark by a word gap, if any mark was sent before
+    fn separate_word(&mut self) {
+        if self.gap.is_some() {
+            self.gap = Some(Element::WordGap);
+        }
+    }
+}
+
+/// Keys the elements of a message one by one, see [`Steps::key`]
+pub(crate) struct Steps<'k, I: Iterator<Item = Element>> {
+    keyer: &'k mut Keyer,
+    elements: Peekable<I>,
+    /// Gap separating this message from the previous one
+    gap: Option<Element>,
+    /// Whether the sink is keyed down
+    keyed: bool,
+    /// Whether the next message is separated by a word gap
+    word_gap: bool,
+}
+
+impl<I: Iterator<Item = Element>> Steps<'_, I> {
+    /// Key `sink` for the next element
+    ///
+    /// Returns the time in ms to wait before the next call, or `None` once
+    /// the message is complete and the sink is keyed up.
+    pub(crate) fn key<S: KeySink>(&mut self, sink: &mut S) -> Result<Option<u16>, S::Error> {
+        if self.keyed {
+            self.keyed = false;
+            sink.key_up()?;
+        }
+        let element = match self.gap.take().or_else(|| self.elements.next()) {
+            Some(element) => element,
+            None => {
+                if self.word_gap {
+                    self.keyer.separate_word();
+                }
+                return Ok(None);
+            }
+        };
+        let duration = element.duration(&self.keyer.timing);
+        if element.is_mark() {
+            sink.key_down()?;
+            self.keyed = true;
+            self.keyer.gap = Some(Element::CharGap);
+        }
+        sink.hold(duration);
+        Ok(Some(duration))
+    }
+}
@@ -1,223 +1,548 @@
 //! Morse output for embedded platforms
 //!
-//! # Limitations
+//! # Supported characters
 //!
-//! Only supports 'a-zA-Z '
+//! Letters `a-zA-Z`, digits `0-9`, space and the punctuation
+//! `. , ? ' ! / ( ) & : ; = + - _ " $ @` as defined by ITU-R M.1677.
+//! Other characters are skipped, substituted or rejected, see [`Unsupported`].
+//! Whitespace separates words.
 //!
-//! # Example
+//! Tables for Latin letters with diacritics (like `Ä`, `É` or `Ñ`), Cyrillic,
+//! Greek, Hebrew, Arabic, Japanese Wabun and American Morse are available
+//! behind features, see [`tables`], other alphabets can be added with a
+//! [`CodeTable`].
 //!
-//! ```
-//! let pin = …;
-//! let delay = …;
+//! # Prosigns
+//!
+//! Characters enclosed in angle brackets are sent as one run without
+//! inter-character gaps, so `"<AR>"` sends the end of message prosign `.-.-.`.
+//! See also [`Prosign`] and [`Morse::output_prosign`].
+//!
+//! # Timing
+//!
+//! Marks and gaps follow the standard 1/3/7 unit scheme, see [`Timing`].
+//!
+//! # Elements
+//!
+//! [`encode`] splits a message into dots, dashes and gaps without any
+//! hardware, e.g. for driving DMA or LED strips, [`Elements::timed`] adds
+//! their durations. All outputs of this crate are built on it.
+//!
+//! [`Notation`] renders messages as dots and dashes, like `".- -... / ..."`,
+//! and parses such notation back into text.
+//!
+//! Fixed messages can be encoded at compile time with [`morse!`], into an
+//! [`Encoded`] message played back with [`Morse::output_encoded`].
+//!
+//! # Outputs
+//!
+//! Messages are keyed on a [`KeySink`], normally an output pin. [`PwmTone`]
+//! drives a buzzer or speaker from a PWM channel instead, with the same
+//! timing, and several sinks can be keyed at once. [`Synth`] renders messages
+//! into audio samples, e.g. for an I2S DAC.
+//!
+//! # Non-blocking output
+//!
+//! [`MorseTransmitter`] outputs a message without blocking, driven by a
+//! timer or main loop instead of a delay.
 //!
+//! # embedded-hal versions
+//!
+//! Both embedded-hal 0.2 (feature `eh0`, enabled by default) and 1.0
+//! (feature `eh1`) are supported, see [`hal`].
+//!
+//! # Async output
+//!
+//! With the `async` feature, `asynch::Morse` provides the same output on
+//! embedded-hal-async, e.g. for running as an Embassy task.
+//!
+//! # Decoding
+//!
+//! [`Decoder`] turns the durations of marks and spaces back into text, using
+//! the same character table. [`PinDecoder`] feeds it by sampling an input pin,
+//! [`ToneDetector`] by detecting a tone in audio samples.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // `pin` and `delay` are provided by the HAL
 //! let mut morse = Morse::new_default(delay, pin, false);
-//! morse.output_str("Hello World");
+//! morse.output_str("Hello World").unwrap();
 //! ```
 #![no_std]
 
-use embedded_hal::{blocking::delay::DelayMs, digital::v2::OutputPin};
+use hal::{Delay, OutputPin};
+use keyer::{Keyer, Steps};
+
+#[cfg(feature = "async")]
+pub mod asynch;
+mod decoder;
+mod encoded;
+mod encoder;
+mod error;
+mod goertzel;
+pub mod hal;
+mod keyer;
+mod notation;
+mod pin_decoder;
+mod prosign;
+mod pwm;
+mod sink;
+mod synth;
+mod table;
+pub mod tables;
+mod timing;
+mod transmitter;
+mod trig;
 
-/// 0 is dot, 1 is dash
-#[derive(Debug, Clone, Copy)]
-struct MorseChar {
+pub use decoder::Decoder;
+pub use encoded::{encoded_len, Encoded, EncodedElements};
+pub use encoder::{encode, find_unsupported, Element, Elements, Timed, Unsupported};
+pub use error::Error;
+pub use goertzel::{Goertzel, ToneDetector};
+pub use notation::{Notation, Parsed, Rendered};
+pub use pin_decoder::PinDecoder;
+pub use prosign::Prosign;
+#[cfg(feature = "eh0")]
+pub use pwm::PwmChannel;
+pub use pwm::PwmTone;
+pub use sink::{KeySink, PinSink, RecordingSink};
+pub use synth::{Samples, Synth};
+pub use table::{CodeTable, International, Table};
+pub use timing::{ReferenceWord, Timing, Weighting};
+pub use transmitter::MorseTransmitter;
+
+/// Morse code of a single character, for building a [`Table`]
+///
+/// Holds up to 16 marks, enough for the longest prosign (SOS, 9 elements).
+/// Codes with long dashes or internal spaces hold up to 8 marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MorseChar {
+    /// Number of marks, with `WIDE` set for codes using two bits per mark
     length: u8,
-    pattern: u8,
+    /// Bit `n` is set if mark `n` is preceded by an internal space, only for
+    /// wide codes
+    spaces: u8,
+    /// 0 is dot, 1 is dash, starting with the least significant bit
+    ///
+    /// Wide codes use two bits per mark, with 2 for a long dash and 3 for an
+    /// extra long dash.
+    pattern: u16,
 }
 
-const CHARS: [MorseChar; 26] = [
+/// Flag in `MorseChar::length` for codes with long dashes or internal spaces,
+/// so international codes fit into one bit per mark
+const WIDE: u8 = 0x80;
+
+/// Placeholder for ASCII characters without a morse representation
+const UNSUPPORTED: MorseChar = MorseChar {
+    length: 0,
+    spaces: 0,
+    pattern: 0,
+};
+
+/// Offset of the first entry in `CHARS`
+const CHARS_START: char = '!';
+
+/// Characters from '!' up to '_', indexed by their ASCII value
+const CHARS: [MorseChar; 63] = [
+    // !
+    MorseChar::new("-.-.--"),
+    // "
+    MorseChar::new(".-..-."),
+    // #
+    UNSUPPORTED,
+    // $
+    MorseChar::new("...-..-"),
+    // %
+    UNSUPPORTED,
+    // &
+    MorseChar::new(".-..."),
+    // '
+    MorseChar::new(".----."),
+    // (
+    MorseChar::new("-.--."),
+    // )
+    MorseChar::new("-.--.-"),
+    // *
+    UNSUPPORTED,
+    // +
+    MorseChar::new(".-.-."),
+    // ,
+    MorseChar::new("--..--"),
+    // -
+    MorseChar::new("-....-"),
+    // .
+    MorseChar::new(".-.-.-"),
+    // /
+    MorseChar::new("-..-."),
+    // 0
+    MorseChar::new("-----"),
+    // 1
+    MorseChar::new(".----"),
+    // 2
+    MorseChar::new("..---"),
+    // 3
+    MorseChar::new("...--"),
+    // 4
+    MorseChar::new("....-"),
+    // 5
+    MorseChar::new("....."),
+    // 6
+    MorseChar::new("-...."),
+    // 7
+    MorseChar::new("--..."),
+    // 8
+    MorseChar::new("---.."),
+    // 9
+    MorseChar::new("----."),
+    // :
+    MorseChar::new("---..."),
+    // ;
+    MorseChar::new("-.-.-."),
+    // <
+    UNSUPPORTED,
+    // =
+    MorseChar::new("-...-"),
+    // >
+    UNSUPPORTED,
+    // ?
+    MorseChar::new("..--.."),
+    // @
+    MorseChar::new(".--.-."),
     // A
-    MorseChar {
-        length: 2,
-        pattern: 0b10,
-    },
+    MorseChar::new(".-"),
     // B
-    MorseChar {
-        length: 4,
-        pattern: 0b0001,
-    },
+    MorseChar::new("-..."),
     // C
-    MorseChar {
-        length: 4,
-        pattern: 0b0101,
-    },
+    MorseChar::new("-.-."),
     // D
-    MorseChar {
-        length: 3,
-        pattern: 0b001,
-    },
+    MorseChar::new("-.."),
     // E
-    MorseChar {
-        length: 1,
-        pattern: 0b0,
-    },
+    MorseChar::new("."),
     // F
-    MorseChar {
-        length: 4,
-        pattern: 0b0100,
-    },
+    MorseChar::new("..-."),
     // G
-    MorseChar {
-        length: 3,
-        pattern: 0b011,
-    },
+    MorseChar::new("--."),
     // H
-    MorseChar {
-        length: 4,
-        pattern: 0b0000,
-    },
+    MorseChar::new("...."),
     // I
-    MorseChar {
-        length: 2,
-        pattern: 0b00,
-    },
+    MorseChar::new(".."),
     // J
-    MorseChar {
-        length: 4,
-        pattern: 0b1110,
-    },
+    MorseChar::new(".---"),
     // K
-    MorseChar {
-        length: 3,
-        pattern: 0b101,
-    },
+    MorseChar::new("-.-"),
     // L
-    MorseChar {
-        length: 4,
-        pattern: 0b0010,
-    },
+    MorseChar::new(".-.."),
     // M
-    MorseChar {
-        length: 2,
-        pattern: 0b11,
-    },
+    MorseChar::new("--"),
     // N
-    MorseChar {
-        length: 2,
-        pattern: 0b01,
-    },
+    MorseChar::new("-."),
     // O
-    MorseChar {
-        length: 3,
-        pattern: 0b111,
-    },
+    MorseChar::new("---"),
     // P
-    MorseChar {
-        length: 4,
-        pattern: 0b0110,
-    },
+    MorseChar::new(".--."),
     // Q
-    MorseChar {
-        length: 4,
-        pattern: 0b1011,
-    },
+    MorseChar::new("--.-"),
     // R
-    MorseChar {
-        length: 3,
-        pattern: 0b010,
-    },
+    MorseChar::new(".-."),
     // S
-    MorseChar {
-        length: 3,
-        pattern: 0b111,
-    },
+    MorseChar::new("..."),
     // T
-    MorseChar {
-        length: 1,
-        pattern: 0b1,
-    },
+    MorseChar::new("-"),
     // U
-    MorseChar {
-        length: 3,
-        pattern: 0b100,
-    },
+    MorseChar::new("..-"),
     // V
-    MorseChar {
-        length: 4,
-        pattern: 0b1000,
-    },
+    MorseChar::new("...-"),
     // W
-    MorseChar {
-        length: 3,
-        pattern: 0b110,
-    },
+    MorseChar::new(".--"),
     // X
-    MorseChar {
-        length: 4,
-        pattern: 0b1001,
-    },
+    MorseChar::new("-..-"),
     // Y
-    MorseChar {
-        length: 4,
-        pattern: 0b1101,
-    },
+    MorseChar::new("-.--"),
     // Z
-    MorseChar {
-        length: 4,
-        pattern: 0b0011,
-    },
+    MorseChar::new("--.."),
+    // [
+    UNSUPPORTED,
+    // \
+    UNSUPPORTED,
+    // ]
+    UNSUPPORTED,
+    // ^
+    UNSUPPORTED,
+    // _
+    MorseChar::new("..--.-"),
 ];
 
-pub struct Morse<DELAY, PIN> {
-    dot_length: u16,
-    dash_length: u16,
-    space_length: u16,
+impl MorseChar {
+    /// Parse a code like `".-"`
+    ///
+    /// Besides `.` and `-`, American Morse codes may contain `_` for a long
+    /// dash (`L`), `=` for an extra long dash (`0`) and single spaces
+    /// between marks for internal spaces, like `".. ."` for `C`.
+    ///
+    /// Panics if the code is invalid (see [`parse`](Self::parse)), at compile
+    /// time when used in a constant.
+    pub const fn new(code: &str) -> Self {
+        match Self::parse(code) {
+            Some(morse_char) => morse_char,
+            None => panic!("invalid morse code"),
+        }
+    }
+
+    /// Parse a code like `".-"`, see [`new`](Self::new)
+    ///
+    /// Returns `None` if the code is empty, has more than 16 marks (8 with
+    /// long dashes or internal spaces), contains other symbols or spaces that
+    /// aren't single and between marks.
+    pub const fn parse(code: &str) -> Option<Self> {
+        let code = code.as_bytes();
+        let mut wide = false;
+        let mut i = 0;
+        while i < code.len() {
+            wide |= matches!(code[i], b'_' | b'=' | b' ');
+            i += 1;
+        }
+        let (bits, max) = if wide { (2, 8) } else { (1, 16) };
+        let mut morse_char = UNSUPPORTED;
+        let mut space = false;
+        let mut i = 0;
+        while i < code.len() {
+            let mark = match code[i] {
+                b'.' => 0,
+                b'-' => 1,
+                b'_' => 2,
+                b'=' => 3,
+                b' ' if !space && morse_char.length != 0 => {
+                    space = true;
+                    i += 1;
+                    continue;
+                }
+                _ => return None,
+            };
+            if morse_char.length == max {
+                return None;
+            }
+            morse_char.pattern |= mark << (bits * morse_char.length);
+            if space {
+                morse_char.spaces |= 1 << morse_char.length;
+                space = false;
+            }
+            morse_char.length += 1;
+            i += 1;
+        }
+        if morse_char.length == 0 || space {
+            return None;
+        }
+        if wide {
+            morse_char.length |= WIDE;
+        }
+        Some(morse_char)
+    }
+
+    /// Number of marks
+    const fn len(self) -> u8 {
+        self.length & !WIDE
+    }
+
+    /// Split off the first mark, as 0 for a dot, 1 for a dash, 2 for a long
+    /// dash and 3 for an extra long dash
+    const fn pop(self) -> (u8, Self) {
+        let (bits, mask) = if self.length & WIDE != 0 {
+            (2, 0b11)
+        } else {
+            (1, 0b1)
+        };
+        let rest = Self {
+            length: self.length - 1,
+            spaces: self.spaces >> 1,
+            pattern: self.pattern >> bits,
+        };
+        ((self.pattern & mask) as u8, rest)
+    }
+
+    /// Whether the first mark is preceded by an internal space
+    const fn is_spaced(self) -> bool {
+        self.spaces & 0b1 == 1
+    }
+
+    /// Append a dot (`dash == false`) or dash, `None` if it doesn't fit
+    fn push(self, dash: bool) -> Option<Self> {
+        if self.length == 16 {
+            return None;
+        }
+        Some(Self {
+            length: self.length + 1,
+            spaces: 0,
+            pattern: self.pattern | (dash as u16) << self.length,
+        })
+    }
+
+    /// Look up the morse representation of an (uppercase) character
+    fn from_char(c: char) -> Option<Self> {
+        let index = (c as usize).checked_sub(CHARS_START as usize)?;
+        CHARS.get(index).copied().filter(|m| m.length != 0)
+    }
+
+    /// Look up the character with this representation
+    fn to_char(self) -> Option<char> {
+        let index = CHARS.iter().position(|m| m.length != 0 && *m == self)?;
+        Some((CHARS_START as u8 + index as u8) as char)
+    }
+}
+
+/// Morse output on a pin or another [`KeySink`]
+///
+/// See [`Timing`] for the exact durations of marks and gaps.
+pub struct Morse<DELAY, SINK> {
+    keyer: Keyer,
     delay: DELAY,
-    pin: PIN,
-    invert: bool,
+    sink: SINK,
 }
 
-impl<ERR, DELAY: DelayMs<u16>, PIN: OutputPin<Error = ERR>> Morse<DELAY, PIN> {
+impl<DELAY, PIN: OutputPin<HAL>, HAL> Morse<DELAY, PinSink<PIN, HAL>> {
     /// Create a new morse instance with a configurable dot_length in ms
     /// `invert` inverts the output signal, so that the output is set low, when it's active
     pub fn new(delay: DELAY, pin: PIN, invert: bool, dot_length: u16) -> Self {
-        Self {
-            dot_length,
-            dash_length: dot_length * 3,
-            space_length: dot_length * 3,
-            delay,
-            pin,
-            invert,
-        }
+        Self::with_timing(delay, pin, invert, Timing::from_dot_length(dot_length))
     }
     /// Create a new morse instance with a `dot_length` of 300 ms
     /// `invert` inverts the output signal, so that the output is set low, when it's active
     pub fn new_default(delay: DELAY, pin: PIN, invert: bool) -> Self {
         Self::new(delay, pin, invert, 300)
     }
+    /// Create a new morse instance with a speed of `wpm` words per minute
+    /// `invert` inverts the output signal, so that the output is set low, when it's active
+    pub fn new_wpm(
+        delay: DELAY,
+        pin: PIN,
+        invert: bool,
+        wpm: u16,
+        reference: ReferenceWord,
+    ) -> Self {
+        Self::with_timing(delay, pin, invert, Timing::from_wpm(wpm, reference))
+    }
+    /// Create a new morse instance with custom timing
+    /// `invert` inverts the output signal, so that the output is set low, when it's active
+    pub fn with_timing(delay: DELAY, pin: PIN, invert: bool, timing: Timing) -> Self {
+        Self::with_sink(delay, PinSink::new(pin, invert), timing)
+    }
+}
+
+impl<DELAY, SINK: KeySink> Morse<DELAY, SINK> {
+    /// Create a new morse instance keying `sink`, e.g. a [`PwmTone`]
+    pub fn with_sink(delay: DELAY, sink: SINK, timing: Timing) -> Self {
+        Self {
+            keyer: Keyer::new(timing),
+            delay,
+            sink,
+        }
+    }
+
+    /// Release the delay and the sink
+    pub fn free(self) -> (DELAY, SINK) {
+        (self.delay, self.sink)
+    }
+
+    /// Currently used timing
+    pub fn timing(&self) -> Timing {
+        self.keyer.timing()
+    }
+
+    /// Change the timing for following messages
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.keyer.set_timing(timing);
+    }
+
+    /// Change the speed to `wpm` words per minute for following messages
+    ///
+    /// Keeps the current weighting.
+    pub fn set_wpm(&mut self, wpm: u16, reference: ReferenceWord) {
+        self.keyer.set_wpm(wpm, reference);
+    }
+
+    /// Switch to Farnsworth timing for following messages, see
+    /// [`Timing::farnsworth`]
+    ///
+    /// Keeps the current weighting.
+    pub fn set_farnsworth(&mut self, char_wpm: u16, effective_wpm: u16, reference: ReferenceWord) {
+        self.keyer
+            .set_farnsworth(char_wpm, effective_wpm, reference);
+    }
+
+    /// Change the weighting for following messages, see [`Weighting`]
+    pub fn set_weighting(&mut self, weighting: Weighting) {
+        self.keyer.set_weighting(weighting);
+    }
+
+    /// Effective speed in words per minute
+    pub fn wpm(&self, reference: ReferenceWord) -> u16 {
+        self.keyer.timing().wpm(reference)
+    }
+
+    /// Encode following messages with `table`, e.g. one of
+    /// [`tables`] or a static array of `(character, code)` pairs, see
+    /// [`CodeTable`]
+    pub fn set_table(&mut self, table: &'static dyn CodeTable) {
+        self.keyer.set_table(table);
+    }
+
+    /// Change how characters without a morse representation are handled,
+    /// see [`Unsupported`]
+    pub fn set_unsupported(&mut self, policy: Unsupported) {
+        self.keyer.set_unsupported(policy);
+    }
 
     /// Output a string as a morse message
     ///
-    /// Only supports 'a-zA-Z '
-    pub fn output_str(&mut self, output: &str) -> Result<(), ERR> {
-        for c in output.chars() {
-            let c = c.to_ascii_uppercase();
-            if c.is_ascii_uppercase() {
-                let morse_char = CHARS[c as usize - 0x41];
-                let mut pattern = morse_char.pattern;
-                for _ in 0..morse_char.length {
-                    if self.invert {
-                        self.pin.set_low()?;
-                    } else {
-                        self.pin.set_high()?;
-                    }
-                    self.delay.delay_ms(if pattern & 0b1 == 1 {
-                        self.dash_length
-                    } else {
-                        self.dot_length
-                    });
-                    if self.invert {
-                        self.pin.set_high()?;
-                    } else {
-                        self.pin.set_low()?;
-                    }
-                    pattern = pattern >> 1;
-                    self.delay.delay_ms(self.dot_length);
-                }
-                self.delay.delay_ms(self.space_length);
-            } else if c == ' ' {
-                self.delay.delay_ms(self.dot_length * 7);
-            }
-        }
-        Ok(())
+    /// Characters without a morse representation are skipped by default, see
+    /// the crate documentation for the supported set and
+    /// [`set_unsupported`](Self::set_unsupported). Whitespace separates words
+    /// and characters enclosed in `<` and `>` are sent as a prosign.
+    pub fn output_str<HAL>(&mut self, output: &str) -> Result<(), Error<SINK::Error>>
+    where
+        DELAY: Delay<HAL>,
+    {
+        let steps = self.keyer.message(output)?;
+        key(&mut self.delay, &mut self.sink, steps)
+    }
+
+    /// Output a single prosign
+    pub fn output_prosign<HAL>(&mut self, prosign: Prosign) -> Result<(), Error<SINK::Error>>
+    where
+        DELAY: Delay<HAL>,
+    {
+        key(
+            &mut self.delay,
+            &mut self.sink,
+            self.keyer.steps(prosign.elements()),
+        )
+    }
+
+    /// Output a message encoded at compile time, see [`morse!`]
+    pub fn output_encoded<HAL, const N: usize>(
+        &mut self,
+        encoded: &Encoded<N>,
+    ) -> Result<(), Error<SINK::Error>>
+    where
+        DELAY: Delay<HAL>,
+    {
+        key(
+            &mut self.delay,
+            &mut self.sink,
+            self.keyer.steps(encoded.elements()),
+        )
+    }
+}
+
+/// Key `sink` for all `steps`, waiting for each
+fn key<DELAY: Delay<HAL>, SINK: KeySink, HAL>(
+    delay: &mut DELAY,
+    sink: &mut SINK,
+    mut steps: Steps<impl Iterator<Item = Element>>,
+) -> Result<(), Error<SINK::Error>> {
+    while let Some(duration) = steps.key(sink).map_err(Error::Output)? {
+        delay.delay_ms(duration);
     }
+    Ok(())
 }
@@ -0,0 +1,159 @@
+use crate::{Element, Elements, UNSUPPORTED};
+use core::fmt;
+use core::str::Split;
+
+/// Text notation of morse code, like `".- -... / ..."`
+///
+/// Used to render messages as dots and dashes (see [`render`](Self::render))
+/// and to parse such notation back into text (see [`parse`](Self::parse)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Notation {
+    /// Symbol for a dot
+    pub dot: char,
+    /// Symbol for a dash
+    pub dash: char,
+    /// Separator between characters
+    pub char_separator: &'static str,
+    /// Separator between words
+    pub word_separator: &'static str,
+}
+
+impl Default for Notation {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+impl Notation {
+    /// `.` and `-`, characters separated by a space and words by `" / "`
+    pub const STANDARD: Self = Self {
+        dot: '.',
+        dash: '-',
+        char_separator: " ",
+        word_separator: " / ",
+    };
+
+    /// Render a message, following the same rules as
+    /// [`Morse::output_str`](crate::Morse::output_str)
+    ///
+    /// The result implements [`fmt::Display`], so it can be written to any
+    /// [`fmt::Write`] without allocating. Prosigns are rendered as a single
+    /// character. American Morse is rendered with `⸺` and `⸻` for long
+    /// dashes and a space for internal spaces, regardless of the notation,
+    /// and can't be parsed back.
+    pub fn render<'a>(&self, message: &'a str) -> Rendered<'a> {
+        self.render_elements(Elements::new(message))
+    }
+
+    /// Render elements, e.g. of a [`Prosign`](crate::Prosign)
+    pub fn render_elements<'a>(&self, elements: Elements<'a>) -> Rendered<'a> {
+        Rendered {
+            notation: *self,
+            elements,
+        }
+    }
+
+    /// Parse notation back into text
+    ///
+    /// Characters are split at `char_separator`, words at `word_separator`,
+    /// empty characters are ignored. Words are separated by a single `' '`.
+    /// Patterns without a matching character, or containing other symbols
+    /// than `dot` and `dash`, are returned as
+    /// [`char::REPLACEMENT_CHARACTER`].
+    pub fn parse<'a>(&self, notation: &'a str) -> Parsed<'a> {
+        Parsed {
+            notation: *self,
+            words: notation.split(self.word_separator),
+            chars: None,
+            started: false,
+            space_pending: false,
+            next: None,
+        }
+    }
+
+    /// Character of a single pattern like `".-"`
+    fn decode(&self, pattern: &str) -> char {
+        let mut morse_char = UNSUPPORTED;
+        for symbol in pattern.chars() {
+            let dash = if symbol == self.dot {
+                false
+            } else if symbol == self.dash {
+                true
+            } else {
+                return char::REPLACEMENT_CHARACTER;
+            };
+            morse_char = match morse_char.push(dash) {
+                Some(morse_char) => morse_char,
+                None => return char::REPLACEMENT_CHARACTER,
+            };
+        }
+        morse_char.to_char().unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+}
+
+/// A message rendered in text notation, see [`Notation::render`]
+#[derive(Debug, Clone)]
+pub struct Rendered<'a> {
+    notation: Notation,
+    elements: Elements<'a>,
+}
+
+impl fmt::Display for Rendered<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let notation = &self.notation;
+        for element in self.elements.clone() {
+            match element {
+                Element::Dot => fmt::Write::write_char(f, notation.dot)?,
+                Element::Dash => fmt::Write::write_char(f, notation.dash)?,
+                Element::LongDash => f.write_str("⸺")?,
+                Element::ExtraLongDash => f.write_str("⸻")?,
+                Element::ElementGap => {}
+                Element::InternalGap => f.write_str(" ")?,
+                Element::CharGap => f.write_str(notation.char_separator)?,
+                Element::WordGap => f.write_str(notation.word_separator)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the characters of parsed notation, see [`Notation::parse`]
+#[derive(Debug, Clone)]
+pub struct Parsed<'a> {
+    notation: Notation,
+    words: Split<'a, &'static str>,
+    /// Remaining characters of the current word
+    chars: Option<Split<'a, &'static str>>,
+    /// Whether a character was returned yet
+    started: bool,
+    /// Whether the next character starts a new word
+    space_pending: bool,
+    /// Character following a returned word gap
+    next: Option<char>,
+}
+
+impl Iterator for Parsed<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if let Some(c) = self.next.take() {
+            return Some(c);
+        }
+        loop {
+            if let Some(chars) = &mut self.chars {
+                if let Some(pattern) = chars.find(|pattern| !pattern.is_empty()) {
+                    let c = self.notation.decode(pattern);
+                    self.started = true;
+                    if core::mem::replace(&mut self.space_pending, false) {
+                        self.next = Some(c);
+                        return Some(' ');
+                    }
+                    return Some(c);
+                }
+            }
+            let word = self.words.next()?;
+            self.space_pending |= self.started;
+            self.chars = Some(word.split(self.notation.char_separator));
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::decoder::Levels;
+use crate::hal::InputPin;
+use crate::Decoder;
+
+/// Decodes morse by sampling an input pin
+///
+/// [`poll`](Self::poll) has to be called every `sample_period` ms, e.g. from
+/// a timer interrupt. Level changes are only accepted after they have been
+/// stable for a number of samples (see [`set_debounce`](Self::set_debounce)),
+/// the resulting mark and space durations are fed into a [`Decoder`].
+pub struct PinDecoder<PIN> {
+    pin: PIN,
+    invert: bool,
+    sample_period: u16,
+    debounce: u8,
+    /// Debounced level, with durations in ms
+    levels: Levels,
+    /// Consecutive samples differing from the debounced level
+    changed: u8,
+}
+
+impl<PIN> PinDecoder<PIN> {
+    /// Create a new pin decoder, sampled every `sample_period` ms
+    /// `invert` inverts the input signal, so that the input is low, when it's active
+    ///
+    /// Changes are debounced over 2 samples by default.
+    pub fn new(pin: PIN, invert: bool, sample_period: u16, decoder: Decoder) -> Self {
+        Self {
+            pin,
+            invert,
+            sample_period,
+            debounce: 2,
+            levels: Levels::new(decoder, 1000),
+            changed: 0,
+        }
+    }
+
+    /// Only accept level changes that are stable for `samples` samples
+    ///
+    /// Pulses shorter than that are ignored. 0 and 1 disable debouncing.
+    pub fn set_debounce(&mut self, samples: u8) {
+        self.debounce = samples.max(1);
+    }
+
+    /// The decoder fed by this pin decoder, e.g. for its speed estimate
+    pub fn decoder(&self) -> &Decoder {
+        self.levels.decoder()
+    }
+
+    /// Release the pin
+    pub fn free(self) -> PIN {
+        self.pin
+    }
+
+    /// Sample the pin, has to be called every `sample_period` ms
+    ///
+    /// Returns a character once it's complete. The last character of a
+    /// transmission is returned once the following space is as long as a
+    /// word gap.
+    pub fn poll<HAL>(&mut self) -> Result<Option<char>, PIN::Error>
+    where
+        PIN: InputPin<HAL>,
+    {
+        let mark = self.pin.is_high()? != self.invert;
+        let period = u32::from(self.sample_period);
+        if mark == self.levels.mark() {
+            // Shorter pulses are part of the current level
+            let elapsed = u32::from(self.changed) * period + period;
+            self.changed = 0;
+            return Ok(self.levels.extend(elapsed));
+        }
+        self.changed += 1;
+        if self.changed < self.debounce {
+            return Ok(None);
+        }
+        // The new level started with the first differing sample
+        let elapsed = u32::from(self.changed) * period;
+        self.changed = 0;
+        Ok(self.levels.change(mark, elapsed))
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{Elements, MorseChar};
+
+/// Procedural signals, sent as one character without inter-character gaps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prosign {
+    /// End of message (AR)
+    Ar,
+    /// Wait (AS)
+    As,
+    /// Break (BK)
+    Bk,
+    /// New section (BT)
+    Bt,
+    /// Closing station (CL)
+    Cl,
+    /// Start of transmission (KA)
+    Ka,
+    /// Invitation for a specific station to transmit (KN)
+    Kn,
+    /// End of contact (SK)
+    Sk,
+    /// Understood (SN)
+    Sn,
+    /// Distress signal (SOS)
+    Sos,
+    /// Error, eight dots (HH)
+    Error,
+}
+
+impl Prosign {
+    /// Letters making up the prosign, as written between angle brackets
+    pub const fn letters(self) -> &'static str {
+        match self {
+            Prosign::Ar => "AR",
+            Prosign::As => "AS",
+            Prosign::Bk => "BK",
+            Prosign::Bt => "BT",
+            Prosign::Cl => "CL",
+            Prosign::Ka => "KA",
+            Prosign::Kn => "KN",
+            Prosign::Sk => "SK",
+            Prosign::Sn => "SN",
+            Prosign::Sos => "SOS",
+            Prosign::Error => "HH",
+        }
+    }
+
+    /// Elements of the prosign, see [`encode`](crate::encode)
+    pub fn elements(self) -> Elements<'static> {
+        Elements::from_morse_char(self.morse_char())
+    }
+
+    pub(crate) const fn morse_char(self) -> MorseChar {
+        match self {
+            Prosign::Ar => MorseChar::new(".-.-."),
+            Prosign::As => MorseChar::new(".-..."),
+            Prosign::Bk => MorseChar::new("-...-.-"),
+            Prosign::Bt => MorseChar::new("-...-"),
+            Prosign::Cl => MorseChar::new("-.-..-.."),
+            Prosign::Ka => MorseChar::new("-.-.-"),
+            Prosign::Kn => MorseChar::new("-.--."),
+            Prosign::Sk => MorseChar::new("...-.-"),
+            Prosign::Sn => MorseChar::new("...-."),
+            Prosign::Sos => MorseChar::new("...---..."),
+            Prosign::Error => MorseChar::new("........"),
+        }
+    }
+}
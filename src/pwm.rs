@@ -0,0 +1,147 @@
+#[cfg(feature = "eh0")]
+use crate::hal::Eh0;
+use crate::hal::Pwm;
+use crate::KeySink;
+use core::marker::PhantomData;
+
+/// Tone output on a PWM channel, for driving a passive buzzer or speaker
+///
+/// A [`KeySink`] that enables the PWM output with the configured duty cycle
+/// during marks and turns it off otherwise. It can be passed to
+/// [`Morse`](crate::Morse) or [`MorseTransmitter`](crate::MorseTransmitter)
+/// instead of a pin, to get the exact same timing as a keyed pin.
+///
+/// The tone frequency is the PWM frequency, usually 600-800 Hz. The
+/// embedded-hal traits for a single channel can't change it, so it's either
+/// configured through the HAL up front or set by a function passed to
+/// [`with_frequency`](Self::with_frequency). Supports
+/// - embedded-hal 0.2 `PwmPin` with `u16` duty, with the `eh0` feature
+/// - embedded-hal 0.2 `Pwm` with `u16` duty, as a `PwmChannel`, with the
+///   `eh0` feature
+/// - embedded-hal 1.0 `SetDutyCycle`, with the `eh1` feature
+pub struct PwmTone<PWM, HAL> {
+    pwm: PWM,
+    duty_percent: u8,
+    frequency: u32,
+    set_frequency: Option<fn(&mut PWM, u32)>,
+    hal: PhantomData<HAL>,
+}
+
+impl<PWM: Pwm<HAL>, HAL> PwmTone<PWM, HAL> {
+    /// Create a tone output with a duty cycle of `duty_percent` while active
+    ///
+    /// A 50% duty cycle is the loudest for most buzzers, lower values can be
+    /// used to reduce the volume.
+    pub fn new(pwm: PWM, duty_percent: u8) -> Self {
+        Self {
+            pwm,
+            duty_percent: duty_percent.min(100),
+            frequency: 0,
+            set_frequency: None,
+            hal: PhantomData,
+        }
+    }
+
+    /// Create a tone output at `frequency` Hz
+    ///
+    /// `set_frequency` is called with the PWM and the frequency, right away
+    /// and on every [`set_frequency`](Self::set_frequency). For example
+    /// with a `PwmChannel`, `|channel, hz| channel.pwm.set_period(hz.Hz())`
+    /// with the HAL's time units.
+    pub fn with_frequency(
+        pwm: PWM,
+        duty_percent: u8,
+        frequency: u32,
+        set_frequency: fn(&mut PWM, u32),
+    ) -> Self {
+        let mut tone = Self::new(pwm, duty_percent);
+        tone.set_frequency = Some(set_frequency);
+        tone.set_frequency(frequency);
+        tone
+    }
+
+    /// Change the frequency for following tones
+    ///
+    /// Does nothing, unless created by [`with_frequency`](Self::with_frequency).
+    pub fn set_frequency(&mut self, frequency: u32) {
+        if let Some(set_frequency) = self.set_frequency {
+            self.frequency = frequency;
+            set_frequency(&mut self.pwm, frequency);
+        }
+    }
+
+    /// Current frequency in Hz, if set by this tone output
+    pub fn frequency(&self) -> Option<u32> {
+        self.set_frequency.map(|_| self.frequency)
+    }
+
+    /// Change the duty cycle for following tones
+    pub fn set_duty_percent(&mut self, duty_percent: u8) {
+        self.duty_percent = duty_percent.min(100);
+    }
+
+    /// Release the PWM channel
+    pub fn free(self) -> PWM {
+        self.pwm
+    }
+}
+
+impl<PWM: Pwm<HAL>, HAL> KeySink for PwmTone<PWM, HAL> {
+    type Error = PWM::Error;
+
+    fn key_down(&mut self) -> Result<(), Self::Error> {
+        let max_duty = self.pwm.max_duty();
+        let duty = u32::from(max_duty) * u32::from(self.duty_percent) / 100;
+        self.pwm.set_duty(duty as u16)
+    }
+
+    fn key_up(&mut self) -> Result<(), Self::Error> {
+        self.pwm.set_duty(0)
+    }
+}
+
+/// A channel of an embedded-hal 0.2 `Pwm` peripheral, for [`PwmTone`]
+///
+/// Unlike a `PwmPin`, the peripheral can change the PWM period, e.g. in a
+/// frequency function passed to [`PwmTone::with_frequency`].
+#[cfg(feature = "eh0")]
+pub struct PwmChannel<P: eh0::Pwm> {
+    pub pwm: P,
+    pub channel: P::Channel,
+}
+
+#[cfg(feature = "eh0")]
+impl<P: eh0::Pwm> PwmChannel<P> {
+    /// Use `channel` of `pwm`
+    pub fn new(pwm: P, channel: P::Channel) -> Self {
+        Self { pwm, channel }
+    }
+
+    /// Release the peripheral
+    pub fn free(self) -> P {
+        self.pwm
+    }
+}
+
+#[cfg(feature = "eh0")]
+impl<P> Pwm<Eh0> for PwmChannel<P>
+where
+    P: eh0::Pwm<Duty = u16>,
+    P::Channel: Clone,
+{
+    type Error = core::convert::Infallible;
+
+    fn max_duty(&mut self) -> u16 {
+        self.pwm.get_max_duty()
+    }
+
+    fn set_duty(&mut self, duty: u16) -> Result<(), Self::Error> {
+        if duty == 0 {
+            self.pwm.disable(self.channel.clone());
+        } else {
+            self.pwm.set_duty(self.channel.clone(), duty);
+            self.pwm.enable(self.channel.clone());
+        }
+        Ok(())
+    }
+}
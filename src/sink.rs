@@ -0,0 +1,202 @@
+use crate::hal::OutputPin;
+use core::marker::PhantomData;
+
+/// Output keyed by the morse encoder
+///
+/// [`Morse`](crate::Morse), `asynch::Morse` and
+/// [`MorseTransmitter`](crate::MorseTransmitter) only ever call these methods,
+/// so any kind of output can be plugged in by implementing this trait.
+/// Implemented for
+/// - output pins, with optional inversion, see [`PinSink`]
+/// - PWM channels, see [`PwmTone`](crate::PwmTone)
+/// - several sinks keyed at once, as arrays `[S; N]` and pairs `(A, B)`
+/// - recording the output in memory, see [`RecordingSink`]
+pub trait KeySink {
+    type Error;
+
+    /// Start a mark
+    fn key_down(&mut self) -> Result<(), Self::Error>;
+
+    /// End a mark
+    fn key_up(&mut self) -> Result<(), Self::Error>;
+
+    /// Called with the time in ms the current state is held, before waiting
+    /// for it
+    ///
+    /// Only needed by sinks tracking time themselves, does nothing by default.
+    fn hold(&mut self, _ms: u16) {}
+}
+
+impl<S: KeySink + ?Sized> KeySink for &mut S {
+    type Error = S::Error;
+
+    fn key_down(&mut self) -> Result<(), Self::Error> {
+        (**self).key_down()
+    }
+
+    fn key_up(&mut self) -> Result<(), Self::Error> {
+        (**self).key_up()
+    }
+
+    fn hold(&mut self, ms: u16) {
+        (**self).hold(ms)
+    }
+}
+
+/// Keys all sinks at once, stops at the first error
+impl<S: KeySink, const N: usize> KeySink for [S; N] {
+    type Error = S::Error;
+
+    fn key_down(&mut self) -> Result<(), Self::Error> {
+        self.iter_mut().try_for_each(KeySink::key_down)
+    }
+
+    fn key_up(&mut self) -> Result<(), Self::Error> {
+        self.iter_mut().try_for_each(KeySink::key_up)
+    }
+
+    fn hold(&mut self, ms: u16) {
+        self.iter_mut().for_each(|sink| sink.hold(ms))
+    }
+}
+
+/// Keys both sinks at once, e.g. a pin and a [`PwmTone`](crate::PwmTone)
+impl<A: KeySink, B: KeySink<Error = A::Error>> KeySink for (A, B) {
+    type Error = A::Error;
+
+    fn key_down(&mut self) -> Result<(), Self::Error> {
+        self.0.key_down()?;
+        self.1.key_down()
+    }
+
+    fn key_up(&mut self) -> Result<(), Self::Error> {
+        self.0.key_up()?;
+        self.1.key_up()
+    }
+
+    fn hold(&mut self, ms: u16) {
+        self.0.hold(ms);
+        self.1.hold(ms);
+    }
+}
+
+/// Keys an output pin, high during marks unless inverted
+///
+/// The embedded-hal generation `HAL` is inferred from the pin, see
+/// [`hal`](crate::hal).
+pub struct PinSink<PIN, HAL> {
+    pin: PIN,
+    invert: bool,
+    hal: PhantomData<HAL>,
+}
+
+impl<PIN: OutputPin<HAL>, HAL> PinSink<PIN, HAL> {
+    /// Key `pin`
+    /// `invert` inverts the output signal, so that the output is set low, when it's active
+    pub fn new(pin: PIN, invert: bool) -> Self {
+        Self {
+            pin,
+            invert,
+            hal: PhantomData,
+        }
+    }
+
+    /// Release the pin
+    pub fn free(self) -> PIN {
+        self.pin
+    }
+}
+
+impl<PIN: OutputPin<HAL>, HAL> KeySink for PinSink<PIN, HAL> {
+    type Error = PIN::Error;
+
+    fn key_down(&mut self) -> Result<(), Self::Error> {
+        if self.invert {
+            self.pin.set_low()
+        } else {
+            self.pin.set_high()
+        }
+    }
+
+    fn key_up(&mut self) -> Result<(), Self::Error> {
+        if self.invert {
+            self.pin.set_high()
+        } else {
+            self.pin.set_low()
+        }
+    }
+}
+
+/// Records the output as `(mark, duration in ms)` segments, e.g. for tests
+///
+/// Consecutive segments of the same state are merged, segments of zero
+/// length are dropped. Holds up to `N` segments, later ones are lost.
+#[derive(Debug, Clone)]
+pub struct RecordingSink<const N: usize> {
+    segments: [(bool, u32); N],
+    len: usize,
+    mark: bool,
+    overflow: bool,
+}
+
+impl<const N: usize> RecordingSink<N> {
+    /// Create an empty recording
+    pub const fn new() -> Self {
+        Self {
+            segments: [(false, 0); N],
+            len: 0,
+            mark: false,
+            overflow: false,
+        }
+    }
+
+    /// The recorded segments
+    pub fn timeline(&self) -> &[(bool, u32)] {
+        &self.segments[..self.len]
+    }
+
+    /// Whether segments were lost because the recording was full
+    pub fn is_overflowed(&self) -> bool {
+        self.overflow
+    }
+
+    /// Discard the recording
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.overflow = false;
+    }
+}
+
+impl<const N: usize> Default for RecordingSink<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> KeySink for RecordingSink<N> {
+    type Error = core::convert::Infallible;
+
+    fn key_down(&mut self) -> Result<(), Self::Error> {
+        self.mark = true;
+        Ok(())
+    }
+
+    fn key_up(&mut self) -> Result<(), Self::Error> {
+        self.mark = false;
+        Ok(())
+    }
+
+    fn hold(&mut self, ms: u16) {
+        if ms == 0 || self.overflow {
+            return;
+        }
+        match self.segments[..self.len].last_mut() {
+            Some((mark, duration)) if *mark == self.mark => *duration += u32::from(ms),
+            _ if self.len < N => {
+                self.segments[self.len] = (self.mark, u32::from(ms));
+                self.len += 1;
+            }
+            _ => self.overflow = true,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{trig, Elements, Prosign, Timed, Timing};
+
+/// Audio rendering of morse messages, e.g. for I2S DACs or WAV files
+///
+/// Marks are rendered as a sine tone, with raised cosine ramps at their start
+/// and end to avoid key clicks. Gaps are silent. The timing matches
+/// [`Morse`](crate::Morse) exactly, up to rounding to whole samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Synth {
+    /// Samples per second
+    pub sample_rate: u32,
+    /// Tone frequency in Hz, has to be below half the sample rate
+    pub frequency: u32,
+    /// Peak amplitude of the tone
+    pub amplitude: i16,
+    /// Rise and fall time of marks in ms, limited to half a mark
+    pub ramp: u16,
+}
+
+impl Synth {
+    /// Create a synthesizer with half the full scale amplitude and 5 ms ramps
+    pub const fn new(sample_rate: u32, frequency: u32) -> Self {
+        Self {
+            sample_rate,
+            frequency,
+            amplitude: i16::MAX / 2,
+            ramp: 5,
+        }
+    }
+
+    /// Render a message, see [`Morse::output_str`](crate::Morse::output_str)
+    pub fn render<'a>(&self, message: &'a str, timing: Timing) -> Samples<'a> {
+        self.render_elements(Elements::new(message), timing)
+    }
+
+    /// Render a single prosign
+    pub fn render_prosign(&self, prosign: Prosign, timing: Timing) -> Samples<'static> {
+        self.render_elements(prosign.elements(), timing)
+    }
+
+    /// Render elements, e.g. encoded with another table, see
+    /// [`Elements::with_table`]
+    pub fn render_elements<'a>(&self, elements: Elements<'a>, timing: Timing) -> Samples<'a> {
+        Samples::new(*self, elements.timed(timing))
+    }
+}
+
+/// Iterator over the samples of a rendered message, see [`Synth`]
+#[derive(Debug, Clone)]
+pub struct Samples<'a> {
+    synth: Synth,
+    elements: Timed<'a>,
+    /// Current element
+    mark: bool,
+    index: u32,
+    len: u32,
+    /// Length of the ramps for the current mark, in samples
+    ramp: u32,
+    /// End of the current element since the start of the message, in ms
+    elapsed: u32,
+    phase: u32,
+    step: u32,
+}
+
+impl<'a> Samples<'a> {
+    fn new(synth: Synth, elements: Timed<'a>) -> Self {
+        Self {
+            synth,
+            elements,
+            mark: false,
+            index: 0,
+            len: 0,
+            ramp: 0,
+            elapsed: 0,
+            phase: 0,
+            step: trig::phase_step(synth.frequency, synth.sample_rate),
+        }
+    }
+
+    /// Number of samples in the first `ms` ms of the message
+    fn samples_at(&self, ms: u32) -> u32 {
+        (u64::from(ms) * u64::from(self.synth.sample_rate) / 1000) as u32
+    }
+
+    /// Raised cosine envelope, `t` samples into a ramp, in Q15
+    fn envelope(&self, t: u32) -> i32 {
+        if t >= self.ramp {
+            return 1 << 15;
+        }
+        // Half a turn over the length of the ramp
+        let phase = ((u64::from(t) << 31) / u64::from(self.ramp)) as u32;
+        ((1 << 15) - trig::cos(phase)) / 2
+    }
+}
+
+impl Iterator for Samples<'_> {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        while self.index >= self.len {
+            let (mark, duration) = self.elements.next()?;
+            let start = self.samples_at(self.elapsed);
+            self.elapsed += u32::from(duration);
+            self.mark = mark;
+            self.index = 0;
+            self.len = self.samples_at(self.elapsed) - start;
+            self.ramp = self
+                .samples_at(u32::from(self.synth.ramp))
+                .min(self.len / 2);
+        }
+        let sample = if self.mark {
+            let t = self.index.min(self.len - 1 - self.index);
+            let tone = (i32::from(self.synth.amplitude) * trig::sin(self.phase)) >> 15;
+            ((tone * self.envelope(t)) >> 15) as i16
+        } else {
+            0
+        };
+        self.index += 1;
+        self.phase = self.phase.wrapping_add(self.step);
+        Some(sample)
+    }
+}
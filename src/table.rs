@@ -0,0 +1,182 @@
+use crate::MorseChar;
+use core::fmt;
+
+/// Mapping of characters to morse code
+///
+/// [`International`] is used by default, other tables can be selected with
+/// e.g. [`Morse::set_table`](crate::Morse::set_table) or
+/// [`Elements::with_table`](crate::Elements::with_table). See
+/// [`tables`](crate::tables) for the included ones.
+///
+/// Custom tables can be built with [`Table`], given as arrays of
+/// `(character, code)` pairs like `[('Ä', ".-.-")]` (see [`MorseChar::new`]
+/// for the notation) or implemented from scratch, e.g. to look up codes
+/// stored at runtime.
+pub trait CodeTable {
+    /// Code of `c`
+    fn code(&self, c: char) -> Option<MorseChar>;
+
+    /// Character with this code
+    fn char(&self, code: MorseChar) -> Option<char>;
+
+    /// Characters sent in place of `c`, if it has no code of its own
+    ///
+    /// Used for characters written as several ones, like a kana with a
+    /// voicing mark. Returns `None` by default.
+    fn expand(&self, _c: char) -> Option<&'static str> {
+        None
+    }
+
+    /// Code of `first` followed by `second`, if they're sent as one
+    /// character, like `CH` in German
+    ///
+    /// Takes precedence over the codes of the single characters. Returns
+    /// `None` by default.
+    fn digraph(&self, _first: char, _second: char) -> Option<MorseChar> {
+        None
+    }
+}
+
+impl fmt::Debug for dyn CodeTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CodeTable")
+    }
+}
+
+/// The international (ITU-R M.1677) table, see the crate documentation for
+/// the supported characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct International;
+
+impl CodeTable for International {
+    fn code(&self, c: char) -> Option<MorseChar> {
+        MorseChar::from_char(c.to_ascii_uppercase())
+    }
+
+    fn char(&self, code: MorseChar) -> Option<char> {
+        code.to_char()
+    }
+}
+
+/// Code table of additional characters, on top of the international table
+///
+/// Characters not in the table are looked up in [`International`], so
+/// digits and punctuation don't have to be repeated, unless disabled with
+/// [`without_fallback`](Self::without_fallback). Lowercase characters
+/// are looked up as uppercase. If several characters have the same code,
+/// the first one is returned by [`char`](CodeTable::char).
+#[derive(Debug, Clone, Copy)]
+pub struct Table {
+    chars: &'static [(char, MorseChar)],
+    expansions: &'static [(char, &'static str)],
+    digraphs: &'static [(&'static str, MorseChar)],
+    fallback: bool,
+}
+
+impl Table {
+    /// Create a table from `(character, code)` pairs
+    pub const fn new(chars: &'static [(char, MorseChar)]) -> Self {
+        Self {
+            chars,
+            expansions: &[],
+            digraphs: &[],
+            fallback: true,
+        }
+    }
+
+    /// Same table, without looking up missing characters in the
+    /// international table
+    pub const fn without_fallback(self) -> Self {
+        Self {
+            fallback: false,
+            ..self
+        }
+    }
+
+    /// Same table, with characters sent as several others, see
+    /// [`CodeTable::expand`]
+    pub const fn with_expansions(self, expansions: &'static [(char, &'static str)]) -> Self {
+        Self { expansions, ..self }
+    }
+
+    /// Same table, with pairs of characters sent as one, see
+    /// [`CodeTable::digraph`]
+    ///
+    /// The pairs are given in uppercase, like `("CH", MorseChar::new("----"))`.
+    pub const fn with_digraphs(self, digraphs: &'static [(&'static str, MorseChar)]) -> Self {
+        Self { digraphs, ..self }
+    }
+
+    fn international(&self) -> Option<International> {
+        self.fallback.then_some(International)
+    }
+
+    fn find<T: Copy>(entries: &[(char, T)], c: char) -> Option<T> {
+        let find = |c| {
+            entries
+                .iter()
+                .find(|entry| entry.0 == c)
+                .map(|entry| entry.1)
+        };
+        find(c).or_else(|| {
+            let mut upper = c.to_uppercase();
+            match (upper.next(), upper.next()) {
+                (Some(upper), None) if upper != c => find(upper),
+                _ => None,
+            }
+        })
+    }
+}
+
+impl CodeTable for Table {
+    fn code(&self, c: char) -> Option<MorseChar> {
+        Self::find(self.chars, c).or_else(|| self.international()?.code(c))
+    }
+
+    fn char(&self, code: MorseChar) -> Option<char> {
+        self.chars
+            .iter()
+            .find(|entry| entry.1 == code)
+            .map(|entry| entry.0)
+            .or_else(|| self.international()?.char(code))
+    }
+
+    fn expand(&self, c: char) -> Option<&'static str> {
+        Self::find(self.expansions, c)
+    }
+
+    fn digraph(&self, first: char, second: char) -> Option<MorseChar> {
+        let upper = |c: char| {
+            let mut upper = c.to_uppercase();
+            match (upper.next(), upper.next()) {
+                (Some(upper), None) => upper,
+                _ => c,
+            }
+        };
+        let (first, second) = (upper(first), upper(second));
+        self.digraphs
+            .iter()
+            .find(|entry| entry.0.chars().eq([first, second]))
+            .map(|entry| entry.1)
+    }
+}
+
+/// Codes in text notation, on top of the international table
+///
+/// Behaves like [`Table`], but the codes are parsed on every lookup. Invalid
+/// codes are treated like missing characters.
+impl<const N: usize> CodeTable for [(char, &'static str); N] {
+    fn code(&self, c: char) -> Option<MorseChar> {
+        match Table::find(self, c) {
+            Some(code) => MorseChar::parse(code),
+            None => International.code(c),
+        }
+    }
+
+    fn char(&self, code: MorseChar) -> Option<char> {
+        self.iter()
+            .find(|entry| MorseChar::parse(entry.1) == Some(code))
+            .map(|entry| entry.0)
+            .or_else(|| International.char(code))
+    }
+}
@@ -0,0 +1,422 @@
+//! Code tables for extended Latin and non-Latin alphabets and American Morse
+//!
+//! Each table is behind its own feature, so unused ones don't end up in the
+//! binary:
+//!
+//! | Feature          | Table                                                         |
+//! |------------------|---------------------------------------------------------------|
+//! | `latin-extended` | `LATIN_EXTENDED`, `LATIN_EXTENDED_CH`, `LATIN_TRANSLITERATED` |
+//! | `cyrillic`       | `CYRILLIC`                                                    |
+//! | `greek`          | `GREEK`                                                       |
+//! | `hebrew`         | `HEBREW`                                                      |
+//! | `arabic`         | `ARABIC`                                                      |
+//! | `wabun`          | `Wabun`                                                       |
+//! | `american`       | `AMERICAN`                                                    |
+//!
+//! All of them except `AMERICAN` fall back to the international table for
+//! digits, punctuation and Latin letters.
+
+#[cfg(feature = "wabun")]
+use crate::CodeTable;
+#[cfg(any(
+    feature = "latin-extended",
+    feature = "cyrillic",
+    feature = "greek",
+    feature = "hebrew",
+    feature = "arabic",
+    feature = "wabun",
+    feature = "american",
+))]
+use crate::{MorseChar, Table};
+
+/// Latin letters with diacritics, with their own codes
+///
+/// `CH` is sent as `C` and `H`, see [`LATIN_EXTENDED_CH`] for sending it as
+/// one character. Characters sharing a code are decoded as the first one,
+/// e.g. `..-..` as `É`.
+#[cfg(feature = "latin-extended")]
+pub static LATIN_EXTENDED: Table = Table::new(LATIN);
+
+/// Like [`LATIN_EXTENDED`], but sending `CH` as `----`, as in German, Czech
+/// or Polish
+///
+/// `----` is still decoded as `Ĥ`.
+#[cfg(feature = "latin-extended")]
+pub static LATIN_EXTENDED_CH: Table =
+    Table::new(LATIN).with_digraphs(&[("CH", MorseChar::new("----"))]);
+
+#[cfg(feature = "latin-extended")]
+const LATIN: &[(char, MorseChar)] = &[
+    ('Ä', MorseChar::new(".-.-")),
+    ('Á', MorseChar::new(".--.-")),
+    ('Å', MorseChar::new(".--.-")),
+    ('Ç', MorseChar::new("-.-..")),
+    ('É', MorseChar::new("..-..")),
+    ('Đ', MorseChar::new("..-..")),
+    ('È', MorseChar::new(".-..-")),
+    ('Ð', MorseChar::new("..--.")),
+    ('Ĝ', MorseChar::new("--.-.")),
+    ('Ĥ', MorseChar::new("----")),
+    ('Ĵ', MorseChar::new(".---.")),
+    ('Ñ', MorseChar::new("--.--")),
+    ('Ö', MorseChar::new("---.")),
+    ('Ø', MorseChar::new("---.")),
+    ('Ŝ', MorseChar::new("...-.")),
+    ('Þ', MorseChar::new(".--..")),
+    ('Ü', MorseChar::new("..--")),
+    ('Ŭ', MorseChar::new("..--")),
+];
+
+/// Latin letters with diacritics, transliterated to plain letters
+///
+/// For receivers not knowing the extended codes, e.g. `ä` is sent as `AE`
+/// and `é` as `E`.
+#[cfg(feature = "latin-extended")]
+pub static LATIN_TRANSLITERATED: Table = Table::new(&[]).with_expansions(&[
+    ('Ä', "AE"),
+    ('Á', "A"),
+    ('Å', "AA"),
+    ('Ç', "C"),
+    ('É', "E"),
+    ('Đ', "D"),
+    ('È', "E"),
+    ('Ð', "D"),
+    ('Ĝ', "G"),
+    ('Ĥ', "H"),
+    ('Ĵ', "J"),
+    ('Ñ', "N"),
+    ('Ö', "OE"),
+    ('Ø', "OE"),
+    ('Ŝ', "S"),
+    ('Þ', "TH"),
+    ('Ü', "UE"),
+    ('Ŭ', "U"),
+    ('ß', "SS"),
+]);
+
+/// Russian Cyrillic letters
+#[cfg(feature = "cyrillic")]
+pub static CYRILLIC: Table = Table::new(&[
+    ('А', MorseChar::new(".-")),
+    ('Б', MorseChar::new("-...")),
+    ('В', MorseChar::new(".--")),
+    ('Г', MorseChar::new("--.")),
+    ('Д', MorseChar::new("-..")),
+    ('Е', MorseChar::new(".")),
+    ('Ж', MorseChar::new("...-")),
+    ('З', MorseChar::new("--..")),
+    ('И', MorseChar::new("..")),
+    ('Й', MorseChar::new(".---")),
+    ('К', MorseChar::new("-.-")),
+    ('Л', MorseChar::new(".-..")),
+    ('М', MorseChar::new("--")),
+    ('Н', MorseChar::new("-.")),
+    ('О', MorseChar::new("---")),
+    ('П', MorseChar::new(".--.")),
+    ('Р', MorseChar::new(".-.")),
+    ('С', MorseChar::new("...")),
+    ('Т', MorseChar::new("-")),
+    ('У', MorseChar::new("..-")),
+    ('Ф', MorseChar::new("..-.")),
+    ('Х', MorseChar::new("....")),
+    ('Ц', MorseChar::new("-.-.")),
+    ('Ч', MorseChar::new("---.")),
+    ('Ш', MorseChar::new("----")),
+    ('Щ', MorseChar::new("--.-")),
+    ('Ъ', MorseChar::new("--.--")),
+    ('Ы', MorseChar::new("-.--")),
+    ('Ь', MorseChar::new("-..-")),
+    ('Э', MorseChar::new("..-..")),
+    ('Ю', MorseChar::new("..--")),
+    ('Я', MorseChar::new(".-.-")),
+    // Sent like Е
+    ('Ё', MorseChar::new(".")),
+]);
+
+/// Greek letters
+#[cfg(feature = "greek")]
+pub static GREEK: Table = Table::new(&[
+    ('Α', MorseChar::new(".-")),
+    ('Β', MorseChar::new("-...")),
+    ('Γ', MorseChar::new("--.")),
+    ('Δ', MorseChar::new("-..")),
+    ('Ε', MorseChar::new(".")),
+    ('Ζ', MorseChar::new("--..")),
+    ('Η', MorseChar::new("....")),
+    ('Θ', MorseChar::new("-.-.")),
+    ('Ι', MorseChar::new("..")),
+    ('Κ', MorseChar::new("-.-")),
+    ('Λ', MorseChar::new(".-..")),
+    ('Μ', MorseChar::new("--")),
+    ('Ν', MorseChar::new("-.")),
+    ('Ξ', MorseChar::new("-..-")),
+    ('Ο', MorseChar::new("---")),
+    ('Π', MorseChar::new(".--.")),
+    ('Ρ', MorseChar::new(".-.")),
+    ('Σ', MorseChar::new("...")),
+    ('Τ', MorseChar::new("-")),
+    ('Υ', MorseChar::new("-.--")),
+    ('Φ', MorseChar::new("..-.")),
+    ('Χ', MorseChar::new("----")),
+    ('Ψ', MorseChar::new("--.-")),
+    ('Ω', MorseChar::new(".--")),
+    // Accents aren't sent
+    ('Ά', MorseChar::new(".-")),
+    ('Έ', MorseChar::new(".")),
+    ('Ή', MorseChar::new("....")),
+    ('Ί', MorseChar::new("..")),
+    ('Ό', MorseChar::new("---")),
+    ('Ύ', MorseChar::new("-.--")),
+    ('Ώ', MorseChar::new(".--")),
+]);
+
+/// Hebrew letters
+#[cfg(feature = "hebrew")]
+pub static HEBREW: Table = Table::new(&[
+    ('א', MorseChar::new(".-")),
+    ('ב', MorseChar::new("-...")),
+    ('ג', MorseChar::new("--.")),
+    ('ד', MorseChar::new("-..")),
+    ('ה', MorseChar::new("---")),
+    ('ו', MorseChar::new(".")),
+    ('ז', MorseChar::new("--..")),
+    ('ח', MorseChar::new("....")),
+    ('ט', MorseChar::new("..-")),
+    ('י', MorseChar::new("..")),
+    ('כ', MorseChar::new("-.-")),
+    ('ל', MorseChar::new(".-..")),
+    ('מ', MorseChar::new("--")),
+    ('נ', MorseChar::new("-.")),
+    ('ס', MorseChar::new("-.-.")),
+    ('ע', MorseChar::new(".---")),
+    ('פ', MorseChar::new(".--.")),
+    ('צ', MorseChar::new(".--")),
+    ('ק', MorseChar::new("--.-")),
+    ('ר', MorseChar::new(".-.")),
+    ('ש', MorseChar::new("...")),
+    ('ת', MorseChar::new("-")),
+    // Final forms are sent like the regular ones
+    ('ך', MorseChar::new("-.-")),
+    ('ם', MorseChar::new("--")),
+    ('ן', MorseChar::new("-.")),
+    ('ף', MorseChar::new(".--.")),
+    ('ץ', MorseChar::new(".--")),
+]);
+
+/// Arabic letters
+#[cfg(feature = "arabic")]
+pub static ARABIC: Table = Table::new(&[
+    ('ا', MorseChar::new(".-")),
+    ('ب', MorseChar::new("-...")),
+    ('ت', MorseChar::new("-")),
+    ('ث', MorseChar::new("-.-.")),
+    ('ج', MorseChar::new(".---")),
+    ('ح', MorseChar::new("....")),
+    ('خ', MorseChar::new("---")),
+    ('د', MorseChar::new("-..")),
+    ('ذ', MorseChar::new("--..")),
+    ('ر', MorseChar::new(".-.")),
+    ('ز', MorseChar::new("---.")),
+    ('س', MorseChar::new("...")),
+    ('ش', MorseChar::new("----")),
+    ('ص', MorseChar::new("-..-")),
+    ('ض', MorseChar::new("...-")),
+    ('ط', MorseChar::new("..-")),
+    ('ظ', MorseChar::new("-.--")),
+    ('ع', MorseChar::new(".-.-")),
+    ('غ', MorseChar::new("--.")),
+    ('ف', MorseChar::new("..-.")),
+    ('ق', MorseChar::new("--.-")),
+    ('ك', MorseChar::new("-.-")),
+    ('ل', MorseChar::new(".-..")),
+    ('م', MorseChar::new("--")),
+    ('ن', MorseChar::new("-.")),
+    ('ه', MorseChar::new("..-..")),
+    ('و', MorseChar::new(".--")),
+    ('ي', MorseChar::new("..")),
+    ('ء', MorseChar::new(".")),
+]);
+
+/// Japanese Wabun code
+///
+/// Covers katakana and hiragana, which are sent the same. Voiced kana are
+/// sent as the unvoiced kana followed by the (han)dakuten, like `ガ` as `カ゛`.
+#[cfg(feature = "wabun")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wabun;
+
+#[cfg(feature = "wabun")]
+static WABUN: Table = Table::new(&[
+    ('イ', MorseChar::new(".-")),
+    ('ロ', MorseChar::new(".-.-")),
+    ('ハ', MorseChar::new("-...")),
+    ('ニ', MorseChar::new("-.-.")),
+    ('ホ', MorseChar::new("-..")),
+    ('ヘ', MorseChar::new(".")),
+    ('ト', MorseChar::new("..-..")),
+    ('チ', MorseChar::new("..-.")),
+    ('リ', MorseChar::new("--.")),
+    ('ヌ', MorseChar::new("....")),
+    ('ル', MorseChar::new("-.--.")),
+    ('ヲ', MorseChar::new(".---")),
+    ('ワ', MorseChar::new("-.-")),
+    ('カ', MorseChar::new(".-..")),
+    ('ヨ', MorseChar::new("--")),
+    ('タ', MorseChar::new("-.")),
+    ('レ', MorseChar::new("---")),
+    ('ソ', MorseChar::new("---.")),
+    ('ツ', MorseChar::new(".--.")),
+    ('ネ', MorseChar::new("--.-")),
+    ('ナ', MorseChar::new(".-.")),
+    ('ラ', MorseChar::new("...")),
+    ('ム', MorseChar::new("-")),
+    ('ウ', MorseChar::new("..-")),
+    ('ヰ', MorseChar::new(".-..-")),
+    ('ノ', MorseChar::new("..--")),
+    ('オ', MorseChar::new(".-...")),
+    ('ク', MorseChar::new("...-")),
+    ('ヤ', MorseChar::new(".--")),
+    ('マ', MorseChar::new("-..-")),
+    ('ケ', MorseChar::new("-.--")),
+    ('フ', MorseChar::new("--..")),
+    ('コ', MorseChar::new("----")),
+    ('エ', MorseChar::new("-.---")),
+    ('テ', MorseChar::new(".-.--")),
+    ('ア', MorseChar::new("--.--")),
+    ('サ', MorseChar::new("-.-.-")),
+    ('キ', MorseChar::new("-.-..")),
+    ('ユ', MorseChar::new("-..--")),
+    ('メ', MorseChar::new("-...-")),
+    ('ミ', MorseChar::new("..-.-")),
+    ('シ', MorseChar::new("--.-.")),
+    ('ヱ', MorseChar::new(".--..")),
+    ('ヒ', MorseChar::new("--..-")),
+    ('モ', MorseChar::new("-..-.")),
+    ('セ', MorseChar::new(".---.")),
+    ('ス', MorseChar::new("---.-")),
+    ('ン', MorseChar::new(".-.-.")),
+    // Dakuten and handakuten
+    ('゛', MorseChar::new("..")),
+    ('゜', MorseChar::new("..--.")),
+    // Long vowel mark
+    ('ー', MorseChar::new(".--.-")),
+    ('、', MorseChar::new(".-.-.-")),
+    ('」', MorseChar::new(".-.-..")),
+    ('（', MorseChar::new("-.--.-")),
+    ('）', MorseChar::new(".-..-.")),
+    // Small kana are sent like the regular ones
+    ('ァ', MorseChar::new("--.--")),
+    ('ィ', MorseChar::new(".-")),
+    ('ゥ', MorseChar::new("..-")),
+    ('ェ', MorseChar::new("-.---")),
+    ('ォ', MorseChar::new(".-...")),
+    ('ッ', MorseChar::new(".--.")),
+    ('ャ', MorseChar::new(".--")),
+    ('ュ', MorseChar::new("-..--")),
+    ('ョ', MorseChar::new("--")),
+    ('ヮ', MorseChar::new("-.-")),
+])
+.with_expansions(&[
+    ('ガ', "カ゛"),
+    ('ギ', "キ゛"),
+    ('グ', "ク゛"),
+    ('ゲ', "ケ゛"),
+    ('ゴ', "コ゛"),
+    ('ザ', "サ゛"),
+    ('ジ', "シ゛"),
+    ('ズ', "ス゛"),
+    ('ゼ', "セ゛"),
+    ('ゾ', "ソ゛"),
+    ('ダ', "タ゛"),
+    ('ヂ', "チ゛"),
+    ('ヅ', "ツ゛"),
+    ('デ', "テ゛"),
+    ('ド', "ト゛"),
+    ('バ', "ハ゛"),
+    ('ビ', "ヒ゛"),
+    ('ブ', "フ゛"),
+    ('ベ', "ヘ゛"),
+    ('ボ', "ホ゛"),
+    ('パ', "ハ゜"),
+    ('ピ', "ヒ゜"),
+    ('プ', "フ゜"),
+    ('ペ', "ヘ゜"),
+    ('ポ', "ホ゜"),
+    ('ヴ', "ウ゛"),
+]);
+
+/// Katakana of a hiragana, other characters unchanged
+#[cfg(feature = "wabun")]
+fn katakana(c: char) -> char {
+    match c {
+        '\u{3041}'..='\u{3096}' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+        c => c,
+    }
+}
+
+#[cfg(feature = "wabun")]
+impl CodeTable for Wabun {
+    fn code(&self, c: char) -> Option<MorseChar> {
+        WABUN.code(katakana(c))
+    }
+
+    fn char(&self, code: MorseChar) -> Option<char> {
+        WABUN.char(code)
+    }
+
+    fn expand(&self, c: char) -> Option<&'static str> {
+        WABUN.expand(katakana(c))
+    }
+}
+
+/// American (railroad) Morse
+///
+/// Uses internal spaces, like in `C` (`.. .`), a long dash for `L` and an
+/// extra long dash for `0`. Meant to be used with
+/// [`Weighting::AMERICAN`](crate::Weighting::AMERICAN). Characters missing in
+/// the table are unsupported, not taken from the international table.
+#[cfg(feature = "american")]
+pub static AMERICAN: Table = Table::new(&[
+    ('A', MorseChar::new(".-")),
+    ('B', MorseChar::new("-...")),
+    ('C', MorseChar::new(".. .")),
+    ('D', MorseChar::new("-..")),
+    ('E', MorseChar::new(".")),
+    ('F', MorseChar::new(".-.")),
+    ('G', MorseChar::new("--.")),
+    ('H', MorseChar::new("....")),
+    ('I', MorseChar::new("..")),
+    ('J', MorseChar::new("-.-.")),
+    ('K', MorseChar::new("-.-")),
+    ('L', MorseChar::new("_")),
+    ('M', MorseChar::new("--")),
+    ('N', MorseChar::new("-.")),
+    ('O', MorseChar::new(". .")),
+    ('P', MorseChar::new(".....")),
+    ('Q', MorseChar::new("..-.")),
+    ('R', MorseChar::new(". ..")),
+    ('S', MorseChar::new("...")),
+    ('T', MorseChar::new("-")),
+    ('U', MorseChar::new("..-")),
+    ('V', MorseChar::new("...-")),
+    ('W', MorseChar::new(".--")),
+    ('X', MorseChar::new(".-..")),
+    ('Y', MorseChar::new(".. ..")),
+    ('Z', MorseChar::new("... .")),
+    ('&', MorseChar::new(". ...")),
+    ('1', MorseChar::new(".--.")),
+    ('2', MorseChar::new("..-..")),
+    ('3', MorseChar::new("...-.")),
+    ('4', MorseChar::new("....-")),
+    ('5', MorseChar::new("---")),
+    ('6', MorseChar::new("......")),
+    ('7', MorseChar::new("--..")),
+    ('8', MorseChar::new("-....")),
+    ('9', MorseChar::new("-..-")),
+    ('0', MorseChar::new("=")),
+    ('.', MorseChar::new("..--..")),
+    (',', MorseChar::new(".-.-")),
+    ('?', MorseChar::new("-..-.")),
+    ('!', MorseChar::new("---.")),
+])
+.without_fallback();
@@ -0,0 +1,240 @@
+/// Reference word used to convert between words per minute and dot length
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceWord {
+    /// "PARIS ", 50 units long, the usual reference for plain text
+    Paris,
+    /// "CODEX ", 60 units long, the usual reference for code groups
+    Codex,
+}
+
+impl ReferenceWord {
+    /// Length in units, including the following word gap
+    pub const fn units(self) -> u32 {
+        match self {
+            ReferenceWord::Paris => 50,
+            ReferenceWord::Codex => 60,
+        }
+    }
+
+    /// Number of dots, dashes and element gaps in the word
+    const fn elements(self) -> (u32, u32, u32) {
+        match self {
+            ReferenceWord::Paris => (10, 4, 9),
+            ReferenceWord::Codex => (7, 8, 10),
+        }
+    }
+}
+
+/// Shape of the marks, to compensate for keying delays of transmitters or
+/// relays
+///
+/// The default of 50% weight and a 3:1 dash ratio gives standard timing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Weighting {
+    /// Share of a mark and the following gap, that is spent on the mark, in
+    /// percent of a dot
+    ///
+    /// Above 50, marks are lengthened and the following gaps shortened by the
+    /// same amount, below 50 the other way around, so the overall speed
+    /// stays the same.
+    pub weight: u8,
+    /// Length of a dash compared to a dot, in tenths
+    pub dash_ratio: u8,
+}
+
+impl Default for Weighting {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+impl Weighting {
+    /// 50% weight and a 3:1 dash ratio
+    pub const STANDARD: Self = Self {
+        weight: 50,
+        dash_ratio: 30,
+    };
+
+    /// 50% weight and a 2:1 dash ratio, as used by American Morse
+    ///
+    /// Makes long dashes four and extra long dashes five units long.
+    pub const AMERICAN: Self = Self {
+        weight: 50,
+        dash_ratio: 20,
+    };
+}
+
+/// Durations (in ms) of the parts making up a morse message
+///
+/// Based on the length of a single dot (one unit), following the standard
+/// scheme:
+///
+/// - a dot is one unit long, a dash three units
+/// - elements of a character are separated by one unit
+/// - characters of a word are separated by three units
+/// - words are separated by seven units
+///
+/// With [`Timing::with_weighting`], the length of marks and the following gaps
+/// can be adjusted, see [`Weighting`].
+///
+/// With [`Timing::farnsworth`], the gaps between characters and words are
+/// stretched, but keep their 3:7 ratio.
+///
+/// Gaps are only inserted *between* marks. A message starts with its first
+/// mark and ends as soon as the last mark is over, without a trailing gap.
+/// Whitespace only separates words, so leading, trailing and repeated
+/// whitespace doesn't add any delay. [`Morse`](crate::Morse) separates
+/// consecutive messages by a character gap instead, before the first mark of
+/// the next one. If there is whitespace at the end of the previous message,
+/// the start of the next one or in a message without marks in between, it's a
+/// word gap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    dot: u16,
+    element_gap: u16,
+    char_gap: u16,
+    word_gap: u16,
+    weighting: Weighting,
+}
+
+impl Timing {
+    /// Standard timing with a dot length of `dot_length` ms
+    ///
+    /// Gaps are limited to `u16::MAX` ms.
+    pub const fn from_dot_length(dot_length: u16) -> Self {
+        Self {
+            dot: dot_length,
+            element_gap: dot_length,
+            char_gap: dot_length.saturating_mul(3),
+            word_gap: dot_length.saturating_mul(7),
+            weighting: Weighting::STANDARD,
+        }
+    }
+
+    /// Same timing, with marks and gaps adjusted by `weighting`
+    ///
+    /// Replaces any weighting applied before.
+    pub const fn with_weighting(self, weighting: Weighting) -> Self {
+        Self { weighting, ..self }
+    }
+
+    /// Currently applied weighting
+    pub const fn weighting(&self) -> Weighting {
+        self.weighting
+    }
+
+    /// Standard timing for a speed of `wpm` words per minute
+    ///
+    /// The dot length is rounded to the nearest ms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `wpm` is 0
+    pub const fn from_wpm(wpm: u16, reference: ReferenceWord) -> Self {
+        let units = reference.units() * wpm as u32;
+        Self::from_dot_length(((60_000 + units / 2) / units) as u16)
+    }
+
+    /// Farnsworth timing, sending characters at `char_wpm` words per minute
+    /// but stretching the gaps between characters and words to slow the
+    /// overall speed down to `effective_wpm`
+    ///
+    /// Falls back to standard timing at `char_wpm`, if `effective_wpm` isn't
+    /// slower than that.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `char_wpm` or `effective_wpm` is 0
+    pub const fn farnsworth(char_wpm: u16, effective_wpm: u16, reference: ReferenceWord) -> Self {
+        let timing = Self::from_wpm(char_wpm, reference);
+        if effective_wpm >= char_wpm {
+            return timing;
+        }
+        let (dots, dashes, element_gaps) = reference.elements();
+        let characters = (dots + 3 * dashes + element_gaps) * timing.dot as u32;
+        let word = 60_000 / effective_wpm as u32;
+        // The 4 character gaps and the word gap make up 19 units
+        let spacing = word.saturating_sub(characters);
+        Self {
+            char_gap: ((3 * spacing + 19 / 2) / 19) as u16,
+            word_gap: ((7 * spacing + 19 / 2) / 19) as u16,
+            ..timing
+        }
+    }
+
+    /// Effective speed in words per minute, rounded to the nearest integer
+    ///
+    /// Measured as the duration of the reference word followed by a word gap,
+    /// so it takes all gaps into account.
+    pub const fn wpm(&self, reference: ReferenceWord) -> u16 {
+        let (dots, dashes, element_gaps) = reference.elements();
+        // Characters are separated by four character gaps and one word gap
+        let word = dots * self.dot() as u32
+            + dashes * self.dash() as u32
+            + element_gaps * self.element_gap() as u32
+            + 4 * self.char_gap() as u32
+            + self.word_gap() as u32;
+        if word == 0 {
+            return 0;
+        }
+        ((60_000 + word / 2) / word) as u16
+    }
+
+    /// Length of a dot
+    pub const fn dot(&self) -> u16 {
+        clamp(self.dot as i32 + self.extension())
+    }
+
+    /// Length of a dash
+    pub const fn dash(&self) -> u16 {
+        let dash = self.dot as i32 * self.weighting.dash_ratio as i32 / 10;
+        clamp(dash + self.extension())
+    }
+
+    /// Length of a long dash of American Morse, a dash and two dots
+    pub const fn long_dash(&self) -> u16 {
+        let dash = self.dot as i32 * self.weighting.dash_ratio as i32 / 10;
+        clamp(dash + 2 * self.dot as i32 + self.extension())
+    }
+
+    /// Length of an extra long dash of American Morse, a dash and three dots
+    pub const fn extra_long_dash(&self) -> u16 {
+        let dash = self.dot as i32 * self.weighting.dash_ratio as i32 / 10;
+        clamp(dash + 3 * self.dot as i32 + self.extension())
+    }
+
+    /// Gap between the elements of a character
+    pub const fn element_gap(&self) -> u16 {
+        clamp(self.element_gap as i32 - self.extension())
+    }
+
+    /// Space within a character of American Morse, an element gap and a dot
+    pub const fn internal_gap(&self) -> u16 {
+        clamp(self.element_gap as i32 + self.dot as i32 - self.extension())
+    }
+
+    /// Gap between the characters of a word
+    pub const fn char_gap(&self) -> u16 {
+        clamp(self.char_gap as i32 - self.extension())
+    }
+
+    /// Gap between words
+    pub const fn word_gap(&self) -> u16 {
+        clamp(self.word_gap as i32 - self.extension())
+    }
+
+    /// Amount marks are lengthened and gaps shortened by the weighting
+    const fn extension(&self) -> i32 {
+        self.dot as i32 * (self.weighting.weight as i32 - 50) / 50
+    }
+}
+
+const fn clamp(duration: i32) -> u16 {
+    if duration < 0 {
+        0
+    } else if duration > u16::MAX as i32 {
+        u16::MAX
+    } else {
+        duration as u16
+    }
+}
@@ -0,0 +1,118 @@
+use crate::hal::OutputPin;
+use crate::{Elements, KeySink, PinSink, Prosign, Timed, Timing};
+
+/// Non-blocking morse output, driven by a timer or a main loop
+///
+/// Instead of waiting itself, the transmitter tells the caller when the output
+/// has to change next. Either call [`poll`](Self::poll) at every edge, e.g.
+/// from a one-shot timer interrupt reprogrammed with the returned delay, or
+/// call [`tick`](Self::tick) periodically with the elapsed time.
+///
+/// Uses the same elements and [`Timing`] as [`Morse`](crate::Morse).
+pub struct MorseTransmitter<'a, SINK> {
+    elements: Timed<'a>,
+    sink: SINK,
+    /// Time until the next edge, for `tick`, negative if it was overshot
+    remaining: i32,
+    /// Whether `tick` was called before
+    started: bool,
+    finished: bool,
+}
+
+impl<'a, PIN: OutputPin<HAL>, HAL> MorseTransmitter<'a, PinSink<PIN, HAL>> {
+    /// Create a transmitter for `message`
+    /// `invert` inverts the output signal, so that the output is set low, when it's active
+    ///
+    /// Nothing is output until the first call to [`poll`](Self::poll) or
+    /// [`tick`](Self::tick).
+    pub fn new(pin: PIN, invert: bool, timing: Timing, message: &'a str) -> Self {
+        Self::with_sink(PinSink::new(pin, invert), timing, message)
+    }
+
+    /// Create a transmitter for a single prosign
+    pub fn new_prosign(pin: PIN, invert: bool, timing: Timing, prosign: Prosign) -> Self {
+        Self::with_elements(PinSink::new(pin, invert), timing, prosign.elements())
+    }
+}
+
+impl<'a, SINK: KeySink> MorseTransmitter<'a, SINK> {
+    /// Create a transmitter for `message`, keying `sink`
+    pub fn with_sink(sink: SINK, timing: Timing, message: &'a str) -> Self {
+        Self::with_elements(sink, timing, Elements::new(message))
+    }
+
+    /// Create a transmitter for `elements`, keying `sink`
+    ///
+    /// Allows sending messages with another table or policy for unsupported
+    /// characters, see [`Elements::with_table`].
+    pub fn with_elements(sink: SINK, timing: Timing, elements: Elements<'a>) -> Self {
+        Self {
+            elements: elements.timed(timing),
+            sink,
+            remaining: 0,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Advance to the next element and key the output accordingly
+    ///
+    /// Returns the time in ms until `poll` has to be called again, or `None`
+    /// once the message is complete and the output is inactive.
+    pub fn poll(&mut self) -> Result<Option<u16>, SINK::Error> {
+        match self.elements.next() {
+            Some((mark, duration)) => {
+                self.set(mark)?;
+                self.sink.hold(duration);
+                Ok(Some(duration))
+            }
+            None => {
+                self.set(false)?;
+                self.finished = true;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Advance by `elapsed` ms, for calling at a fixed rate
+    ///
+    /// The first call starts the message. Returns `false` once the message
+    /// is complete. The output only changes on calls to `tick`, so edges are
+    /// delayed by up to one tick period. The delay doesn't add up, following
+    /// elements are shortened by it. Ticks should be shorter than a dot,
+    /// elements shorter than the delay are skipped.
+    pub fn tick(&mut self, elapsed: u16) -> Result<bool, SINK::Error> {
+        if self.finished {
+            return Ok(false);
+        }
+        if self.started {
+            self.remaining -= i32::from(elapsed);
+        }
+        self.started = true;
+        while self.remaining <= 0 {
+            match self.poll()? {
+                Some(duration) => self.remaining += i32::from(duration),
+                None => return Ok(false),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Whether the whole message has been output
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Release the sink
+    pub fn free(self) -> SINK {
+        self.sink
+    }
+
+    fn set(&mut self, active: bool) -> Result<(), SINK::Error> {
+        if active {
+            self.sink.key_down()
+        } else {
+            self.sink.key_up()
+        }
+    }
+}
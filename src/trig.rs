@@ -0,0 +1,52 @@
+//! Fixed-point trigonometry, without relying on a float math library
+
+/// Phase of a quarter turn, a full turn is 2^32
+const QUARTER: u32 = 1 << 30;
+
+/// Sine of `phase` (a full turn being 2^32) in Q15
+pub(crate) fn sin(phase: u32) -> i32 {
+    let quadrant = phase / QUARTER;
+    let mut x = phase % QUARTER;
+    if quadrant % 2 == 1 {
+        x = QUARTER - x;
+    }
+    // Angle in radians, Q30
+    let a = (i64::from(x) * 1_686_629_713) >> 30;
+    let a2 = (a * a) >> 30;
+    // Taylor series up to x^7, within 2e-4 for a quarter turn
+    let mut t = (1 << 30) - a2 / 42;
+    t = (1 << 30) - ((a2 * t) >> 30) / 20;
+    t = (1 << 30) - ((a2 * t) >> 30) / 6;
+    let s = ((a * t) >> 45) as i32;
+    if quadrant >= 2 {
+        -s
+    } else {
+        s
+    }
+}
+
+/// Cosine of `phase` (a full turn being 2^32) in Q15
+pub(crate) fn cos(phase: u32) -> i32 {
+    sin(phase.wrapping_add(QUARTER))
+}
+
+/// Phase advance per sample for `frequency` at `sample_rate`
+pub(crate) fn phase_step(frequency: u32, sample_rate: u32) -> u32 {
+    ((u64::from(frequency) << 32) / u64::from(sample_rate)) as u32
+}
+
+/// Integer square root, rounded down
+pub(crate) fn sqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    // Newton's method, starting above the root
+    let mut x = 1 << ((64 - value.leading_zeros()).div_ceil(2));
+    loop {
+        let next = (x + value / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
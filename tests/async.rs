@@ -0,0 +1,100 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{block_on, blocking_timeline, Recorder, MESSAGES, TIMING};
+use embedded_morse::{asynch, Morse, Prosign, ReferenceWord, Timing, Unsupported, Weighting};
+
+#[test]
+fn matches_blocking() {
+    let timing = Timing::from_wpm(20, ReferenceWord::Paris);
+    for invert in [false, true] {
+        for message in MESSAGES {
+            let blocking = Recorder::new();
+            let mut morse = Morse::with_timing(blocking.delay(), blocking.pin(), invert, timing);
+            morse.output_str(message).unwrap();
+
+            let recorder = Recorder::new();
+            let mut morse =
+                asynch::Morse::with_timing(recorder.delay1(), recorder.pin1(), invert, timing);
+            block_on(morse.output_str(message)).unwrap();
+            assert_eq!(recorder.events(), blocking.events(), "{:?}", message);
+        }
+    }
+}
+
+#[test]
+fn consecutive_messages() {
+    let blocking = Recorder::new();
+    let mut morse = Morse::new(blocking.delay(), blocking.pin(), false, 10);
+    morse.output_str("E").unwrap();
+    morse.output_prosign(Prosign::Ka).unwrap();
+    morse.output_str(" T").unwrap();
+
+    let recorder = Recorder::new();
+    let mut morse = asynch::Morse::new(recorder.delay1(), recorder.pin1(), false, 10);
+    block_on(morse.output_str("E")).unwrap();
+    block_on(morse.output_prosign(Prosign::Ka)).unwrap();
+    block_on(morse.output_str(" T")).unwrap();
+    assert_eq!(recorder.events(), blocking.events());
+    assert_eq!(
+        recorder.timeline(),
+        blocking_timeline("E<KA> T", TIMING, Unsupported::Skip)
+    );
+}
+
+#[test]
+fn prosign() {
+    let recorder = Recorder::new();
+    let mut morse = asynch::Morse::new(recorder.delay1(), recorder.pin1(), false, 10);
+    block_on(morse.output_prosign(Prosign::Ar)).unwrap();
+    assert_eq!(recorder.timeline(), common::run_timeline(".-.-.", 10));
+}
+
+#[test]
+fn encoded() {
+    let recorder = Recorder::new();
+    let mut morse = asynch::Morse::new(recorder.delay1(), recorder.pin1(), false, 10);
+    block_on(morse.output_encoded(&embedded_morse::morse!("<AR>"))).unwrap();
+    assert_eq!(recorder.timeline(), common::run_timeline(".-.-.", 10));
+}
+
+#[test]
+fn timing() {
+    let recorder = Recorder::new();
+    let mut morse = asynch::Morse::new_default(recorder.delay1(), recorder.pin1(), false);
+    assert_eq!(morse.timing(), Timing::from_dot_length(300));
+    morse.set_timing(Timing::from_dot_length(5));
+    block_on(morse.output_str("I")).unwrap();
+    assert_eq!(recorder.timeline(), vec![(true, 5), (false, 5), (true, 5)]);
+}
+
+#[test]
+fn speed() {
+    let recorder = Recorder::new();
+    let mut morse = asynch::Morse::new_wpm(
+        recorder.delay1(),
+        recorder.pin1(),
+        false,
+        25,
+        ReferenceWord::Paris,
+    );
+    assert_eq!(morse.timing(), Timing::from_dot_length(48));
+    let weighting = Weighting {
+        weight: 60,
+        dash_ratio: 35,
+    };
+    morse.set_wpm(12, ReferenceWord::Codex);
+    assert_eq!(morse.wpm(ReferenceWord::Codex), 12);
+    morse.set_farnsworth(15, 8, ReferenceWord::Codex);
+    assert_eq!(morse.wpm(ReferenceWord::Codex), 8);
+    // Changing the speed keeps the weighting
+    morse.set_weighting(weighting);
+    morse.set_wpm(12, ReferenceWord::Codex);
+    assert_eq!(morse.timing().weighting(), weighting);
+    morse.set_farnsworth(15, 8, ReferenceWord::Codex);
+    assert_eq!(
+        morse.timing(),
+        Timing::farnsworth(15, 8, ReferenceWord::Codex).with_weighting(weighting)
+    );
+}
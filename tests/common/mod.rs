@@ -0,0 +1,489 @@
+//! Recording mocks for host-side tests
+
+#![allow(dead_code)]
+
+pub mod wav;
+
+use core::cell::RefCell;
+use embedded_morse::{Morse, Timing, Unsupported};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// A single call made to one of the mocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    High,
+    Low,
+    /// PWM output with this duty, 0 being off
+    Duty(u16),
+    /// PWM period set to this frequency in Hz
+    Frequency(u32),
+    Delay(u16),
+}
+
+/// Shared log of all pin and delay calls, in order
+#[derive(Debug, Clone, Default)]
+pub struct Recorder(Rc<RefCell<Vec<Event>>>);
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pin of the default embedded-hal generation
+    pub fn pin(&self) -> Pin {
+        Pin::from(self.clone())
+    }
+
+    /// Delay of the default embedded-hal generation
+    pub fn delay(&self) -> Delay {
+        Delay::from(self.clone())
+    }
+
+    /// embedded-hal 1.0 pin
+    pub fn pin1(&self) -> MockPin1 {
+        MockPin1(self.clone())
+    }
+
+    /// embedded-hal 1.0 (and embedded-hal-async) delay
+    pub fn delay1(&self) -> MockDelay1 {
+        MockDelay1(self.clone())
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        self.0.borrow().clone()
+    }
+
+    /// Collapse the log into `(level, duration)` segments
+    ///
+    /// The level starts out low, consecutive delays are summed up and
+    /// zero-length segments are dropped, so the result only depends on what
+    /// an observer of the pin would see.
+    pub fn timeline(&self) -> Vec<(bool, u32)> {
+        let mut timeline: Vec<(bool, u32)> = Vec::new();
+        let mut level = false;
+        for event in self.events() {
+            match event {
+                Event::High => level = true,
+                Event::Low => level = false,
+                Event::Duty(duty) => level = duty != 0,
+                Event::Frequency(_) => {}
+                Event::Delay(ms) => match timeline.last_mut() {
+                    Some((last, duration)) if *last == level => *duration += u32::from(ms),
+                    _ => timeline.push((level, u32::from(ms))),
+                },
+            }
+        }
+        timeline.retain(|&(_, duration)| duration != 0);
+        timeline
+    }
+
+    /// embedded-hal 0.2 PWM channel with a maximum duty of `max_duty`
+    pub fn pwm(&self, max_duty: u16) -> MockPwm {
+        MockPwm {
+            recorder: self.clone(),
+            max_duty,
+            duty: 0,
+            enabled: false,
+        }
+    }
+
+    /// embedded-hal 0.2 PWM peripheral with two channels, recording channel 1
+    pub fn pwm_timer(&self, max_duty: u16) -> MockPwmTimer {
+        MockPwmTimer {
+            recorder: self.clone(),
+            max_duty,
+            duty: 0,
+            enabled: false,
+        }
+    }
+
+    /// embedded-hal 1.0 PWM channel with a maximum duty of `max_duty`
+    pub fn pwm1(&self, max_duty: u16) -> MockPwm1 {
+        MockPwm1 {
+            recorder: self.clone(),
+            max_duty,
+        }
+    }
+
+    /// Record a delay without going through a mock, e.g. for the transmitter
+    pub fn wait(&self, ms: u16) {
+        self.push(Event::Delay(ms));
+    }
+
+    fn push(&self, event: Event) {
+        self.0.borrow_mut().push(event);
+    }
+}
+
+/// Mocks used by `Recorder::pin` and `Recorder::delay`, embedded-hal 0.2 if
+/// enabled
+#[cfg(feature = "eh0")]
+pub type Pin = MockPin;
+#[cfg(feature = "eh0")]
+pub type Delay = MockDelay;
+#[cfg(feature = "eh0")]
+pub type Hal = embedded_morse::hal::Eh0;
+#[cfg(not(feature = "eh0"))]
+pub type Pin = MockPin1;
+#[cfg(not(feature = "eh0"))]
+pub type Delay = MockDelay1;
+#[cfg(not(feature = "eh0"))]
+pub type Hal = embedded_morse::hal::Eh1;
+
+/// Input mock of the default embedded-hal generation
+#[cfg(feature = "eh0")]
+pub type Input = MockInput;
+#[cfg(not(feature = "eh0"))]
+pub type Input = MockInput1;
+
+pub fn input(levels: Vec<bool>) -> Input {
+    Input::from(levels)
+}
+
+/// Sample a timeline every `period` ms
+pub fn sample(timeline: &[(bool, u32)], period: u32) -> Vec<bool> {
+    timeline
+        .iter()
+        .flat_map(|&(level, duration)| std::iter::repeat_n(level, (duration / period) as usize))
+        .collect()
+}
+
+macro_rules! from_recorder {
+    ($($mock:ident),*) => {
+        $(impl From<Recorder> for $mock {
+            fn from(recorder: Recorder) -> Self {
+                Self(recorder)
+            }
+        })*
+    };
+}
+
+from_recorder!(MockPin, MockDelay, MockPin1, MockDelay1);
+
+/// Input pin returning its levels one after another, then staying low
+pub struct MockInput(RefCell<VecDeque<bool>>);
+
+#[cfg(feature = "eh0")]
+impl eh0::digital::v2::InputPin for MockInput {
+    type Error = ();
+
+    fn is_high(&self) -> Result<bool, ()> {
+        Ok(self.0.borrow_mut().pop_front().unwrap_or(false))
+    }
+
+    fn is_low(&self) -> Result<bool, ()> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+pub struct MockInput1(RefCell<VecDeque<bool>>);
+
+#[cfg(feature = "eh1")]
+impl eh1::digital::ErrorType for MockInput1 {
+    type Error = core::convert::Infallible;
+}
+
+#[cfg(feature = "eh1")]
+impl eh1::digital::InputPin for MockInput1 {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.0.borrow_mut().pop_front().unwrap_or(false))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+impl From<Vec<bool>> for MockInput {
+    fn from(levels: Vec<bool>) -> Self {
+        Self(RefCell::new(levels.into()))
+    }
+}
+
+impl From<Vec<bool>> for MockInput1 {
+    fn from(levels: Vec<bool>) -> Self {
+        Self(RefCell::new(levels.into()))
+    }
+}
+
+pub struct MockPwm {
+    recorder: Recorder,
+    max_duty: u16,
+    duty: u16,
+    enabled: bool,
+}
+
+#[cfg(feature = "eh0")]
+impl eh0::PwmPin for MockPwm {
+    type Duty = u16;
+
+    fn disable(&mut self) {
+        self.enabled = false;
+        self.recorder.push(Event::Duty(0));
+    }
+
+    fn enable(&mut self) {
+        self.enabled = true;
+        self.recorder.push(Event::Duty(self.duty));
+    }
+
+    fn get_duty(&self) -> u16 {
+        self.duty
+    }
+
+    fn get_max_duty(&self) -> u16 {
+        self.max_duty
+    }
+
+    fn set_duty(&mut self, duty: u16) {
+        self.duty = duty;
+        if self.enabled {
+            self.recorder.push(Event::Duty(duty));
+        }
+    }
+}
+
+pub struct MockPwmTimer {
+    recorder: Recorder,
+    max_duty: u16,
+    duty: u16,
+    enabled: bool,
+}
+
+#[cfg(feature = "eh0")]
+impl eh0::Pwm for MockPwmTimer {
+    type Channel = u8;
+    type Time = u32;
+    type Duty = u16;
+
+    fn disable(&mut self, channel: u8) {
+        if channel == 1 {
+            self.enabled = false;
+            self.recorder.push(Event::Duty(0));
+        }
+    }
+
+    fn enable(&mut self, channel: u8) {
+        if channel == 1 {
+            self.enabled = true;
+            self.recorder.push(Event::Duty(self.duty));
+        }
+    }
+
+    fn get_period(&self) -> u32 {
+        0
+    }
+
+    fn get_duty(&self, _channel: u8) -> u16 {
+        self.duty
+    }
+
+    fn get_max_duty(&self) -> u16 {
+        self.max_duty
+    }
+
+    fn set_duty(&mut self, channel: u8, duty: u16) {
+        if channel == 1 {
+            self.duty = duty;
+            if self.enabled {
+                self.recorder.push(Event::Duty(duty));
+            }
+        }
+    }
+
+    fn set_period<P: Into<u32>>(&mut self, period: P) {
+        self.recorder.push(Event::Frequency(period.into()));
+    }
+}
+
+pub struct MockPwm1 {
+    recorder: Recorder,
+    max_duty: u16,
+}
+
+#[cfg(feature = "eh1")]
+impl eh1::pwm::ErrorType for MockPwm1 {
+    type Error = core::convert::Infallible;
+}
+
+#[cfg(feature = "eh1")]
+impl eh1::pwm::SetDutyCycle for MockPwm1 {
+    fn max_duty_cycle(&self) -> u16 {
+        self.max_duty
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        self.recorder.push(Event::Duty(duty));
+        Ok(())
+    }
+}
+
+pub struct MockPin(Recorder);
+
+#[cfg(feature = "eh0")]
+impl eh0::digital::v2::OutputPin for MockPin {
+    type Error = ();
+
+    fn set_low(&mut self) -> Result<(), ()> {
+        self.0.push(Event::Low);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), ()> {
+        self.0.push(Event::High);
+        Ok(())
+    }
+}
+
+pub struct MockDelay(Recorder);
+
+#[cfg(feature = "eh0")]
+impl eh0::blocking::delay::DelayMs<u16> for MockDelay {
+    fn delay_ms(&mut self, ms: u16) {
+        self.0.push(Event::Delay(ms));
+    }
+}
+
+pub struct MockPin1(Recorder);
+
+#[cfg(feature = "eh1")]
+impl eh1::digital::ErrorType for MockPin1 {
+    type Error = core::convert::Infallible;
+}
+
+#[cfg(feature = "eh1")]
+impl eh1::digital::OutputPin for MockPin1 {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.push(Event::Low);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.push(Event::High);
+        Ok(())
+    }
+}
+
+pub struct MockDelay1(Recorder);
+
+#[cfg(feature = "eh1")]
+impl eh1::delay::DelayNs for MockDelay1 {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.push(Event::Delay((ns / 1_000_000) as u16));
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.0.push(Event::Delay(ms as u16));
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for MockDelay1 {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0.push(Event::Delay((ns / 1_000_000) as u16));
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        self.0.push(Event::Delay(ms as u16));
+    }
+}
+
+/// Run a future to completion, for mocks that never block
+#[cfg(feature = "async")]
+pub fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    use core::task::{Context, Poll, Waker};
+    let mut future = core::pin::pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+/// Messages covering empty output, single marks, words, prosigns and
+/// whitespace
+pub const MESSAGES: &[&str] = &["", "E", "SOS", "CQ DE DL1ABC <AR>", " hello,  world! "];
+
+/// Timing of most reference timelines, with 10 ms dots
+pub const TIMING: Timing = Timing::from_dot_length(10);
+
+/// Timeline of `message` output by a blocking `Morse` on a pin
+pub fn blocking_timeline(message: &str, timing: Timing, policy: Unsupported) -> Vec<(bool, u32)> {
+    let recorder = Recorder::new();
+    let mut morse = Morse::with_timing(recorder.delay(), recorder.pin(), false, timing);
+    morse.set_unsupported(policy);
+    morse.output_str(message).unwrap();
+    recorder.timeline()
+}
+
+/// Timeline of a single run of elements in dot/dash notation
+pub fn run_timeline(code: &str, dot: u32) -> Vec<(bool, u32)> {
+    let mut timeline = Vec::new();
+    for (i, element) in code.chars().enumerate() {
+        if i != 0 {
+            timeline.push((false, dot));
+        }
+        timeline.push((true, if element == '-' { dot * 3 } else { dot }));
+    }
+    timeline
+}
+
+/// Reference table in dot/dash notation, independent from the crate's
+pub const CODES: &[(char, &str)] = &[
+    ('A', ".-"),
+    ('B', "-..."),
+    ('C', "-.-."),
+    ('D', "-.."),
+    ('E', "."),
+    ('F', "..-."),
+    ('G', "--."),
+    ('H', "...."),
+    ('I', ".."),
+    ('J', ".---"),
+    ('K', "-.-"),
+    ('L', ".-.."),
+    ('M', "--"),
+    ('N', "-."),
+    ('O', "---"),
+    ('P', ".--."),
+    ('Q', "--.-"),
+    ('R', ".-."),
+    ('S', "..."),
+    ('T', "-"),
+    ('U', "..-"),
+    ('V', "...-"),
+    ('W', ".--"),
+    ('X', "-..-"),
+    ('Y', "-.--"),
+    ('Z', "--.."),
+    ('0', "-----"),
+    ('1', ".----"),
+    ('2', "..---"),
+    ('3', "...--"),
+    ('4', "....-"),
+    ('5', "....."),
+    ('6', "-...."),
+    ('7', "--..."),
+    ('8', "---.."),
+    ('9', "----."),
+    ('.', ".-.-.-"),
+    (',', "--..--"),
+    ('?', "..--.."),
+    ('\'', ".----."),
+    ('!', "-.-.--"),
+    ('/', "-..-."),
+    ('(', "-.--."),
+    (')', "-.--.-"),
+    ('&', ".-..."),
+    (':', "---..."),
+    (';', "-.-.-."),
+    ('=', "-...-"),
+    ('+', ".-.-."),
+    ('-', "-....-"),
+    ('_', "..--.-"),
+    ('"', ".-..-."),
+    ('$', "...-..-"),
+    ('@', ".--.-."),
+];
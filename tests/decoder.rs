@@ -0,0 +1,112 @@
+mod common;
+
+use common::{blocking_timeline, CODES};
+use embedded_morse::{Decoder, ReferenceWord, Timing, Unsupported};
+
+const DOT: u16 = 50;
+
+fn encode(text: &str) -> Vec<(bool, u32)> {
+    blocking_timeline(text, Timing::from_dot_length(DOT), Unsupported::Skip)
+}
+
+fn decode(decoder: &mut Decoder, timeline: &[(bool, u32)]) -> String {
+    let mut text: String = timeline
+        .iter()
+        .filter_map(|&(mark, duration)| decoder.push(mark, duration as u16))
+        .collect();
+    text.extend(decoder.finish());
+    text
+}
+
+#[test]
+fn every_character() {
+    for &(c, _) in CODES {
+        let text = c.to_string();
+        let mut decoder = Decoder::new(DOT);
+        assert_eq!(decode(&mut decoder, &encode(&text)), text);
+    }
+}
+
+#[test]
+fn words() {
+    let text = "CQ CQ DE DL1ABC/P = TEMP: 21.5C, OK?";
+    let mut decoder = Decoder::new(DOT);
+    assert_eq!(decode(&mut decoder, &encode(text)), text);
+}
+
+#[test]
+fn tolerates_jitter() {
+    let timeline: Vec<_> = encode("PARIS")
+        .into_iter()
+        .enumerate()
+        .map(|(i, (mark, duration))| {
+            // +-30%
+            let jitter = [130, 70, 100, 85, 115][i % 5];
+            (mark, duration * jitter / 100)
+        })
+        .collect();
+    assert_eq!(decode(&mut Decoder::new(DOT), &timeline), "PARIS");
+}
+
+#[test]
+fn unknown_patterns() {
+    let mut decoder = Decoder::new(DOT);
+    // ..--- is '2', ..-- isn't assigned
+    let timeline = common::run_timeline("..--", u32::from(DOT));
+    assert_eq!(decode(&mut decoder, &timeline), "\u{fffd}");
+    // Too long for any character
+    let timeline = common::run_timeline("........", u32::from(DOT));
+    assert_eq!(decode(&mut decoder, &timeline), "\u{fffd}");
+}
+
+#[test]
+fn word_gap_reported_with_next_mark() {
+    let mut decoder = Decoder::new(DOT);
+    assert_eq!(decoder.push(true, DOT), None);
+    assert_eq!(decoder.push(false, DOT * 7), Some('E'));
+    assert_eq!(decoder.push(true, DOT * 3), Some(' '));
+    assert_eq!(decoder.push(false, DOT * 3), Some('T'));
+    assert_eq!(decoder.finish(), None);
+}
+
+fn encode_wpm(text: &str, wpm: u16) -> Vec<(bool, u32)> {
+    let timing = Timing::from_wpm(wpm, ReferenceWord::Paris);
+    blocking_timeline(text, timing, Unsupported::Skip)
+}
+
+#[test]
+fn fixed_decoder_keeps_speed() {
+    let mut decoder = Decoder::new(DOT);
+    decode(&mut decoder, &encode_wpm("PARIS", 12));
+    assert_eq!(decoder.dot_length(), DOT);
+}
+
+#[test]
+fn adaptive_follows_speed() {
+    // Starting at 24 wpm, a fixed decoder would misread 15 wpm
+    let mut decoder = Decoder::adaptive(DOT);
+    let text = "CQ CQ DE DL1ABC";
+    assert_eq!(decode(&mut decoder, &encode_wpm(text, 15)), text);
+    assert_eq!(decoder.wpm(ReferenceWord::Paris), 15);
+
+    let text = "THE QUICK BROWN FOX";
+    assert_eq!(decode(&mut decoder, &encode_wpm(text, 20)), text);
+    assert_eq!(decoder.dot_length(), 60);
+
+    let text = "JUMPS OVER THE LAZY DOG";
+    assert_eq!(decode(&mut decoder, &encode_wpm(text, 12)), text);
+    assert_eq!(decoder.wpm(ReferenceWord::Paris), 12);
+}
+
+#[test]
+fn adaptive_gradual_drift() {
+    let mut decoder = Decoder::adaptive(100);
+    let mut text = String::new();
+    let mut expected = String::new();
+    for wpm in (12..=30).chain((12..30).rev()) {
+        text.push_str(&decode(&mut decoder, &encode_wpm("PARIS ", wpm)));
+        decoder.push(false, 1000);
+        expected.push_str("PARIS");
+    }
+    assert_eq!(text.replace(' ', ""), expected);
+}
@@ -0,0 +1,39 @@
+#![cfg(all(feature = "eh0", feature = "eh1"))]
+
+mod common;
+
+use common::{blocking_timeline, Recorder, TIMING};
+use embedded_morse::{Morse, MorseTransmitter, Timing, Unsupported};
+
+const MESSAGE: &str = "CQ DE DL1ABC <KN>";
+
+fn eh0_events(invert: bool) -> Vec<common::Event> {
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), invert, 10);
+    morse.output_str(MESSAGE).unwrap();
+    recorder.events()
+}
+
+#[test]
+fn morse_matches_eh0() {
+    for invert in [false, true] {
+        let recorder = Recorder::new();
+        let mut morse = Morse::new(recorder.delay1(), recorder.pin1(), invert, 10);
+        morse.output_str(MESSAGE).unwrap();
+        assert_eq!(recorder.events(), eh0_events(invert));
+    }
+}
+
+#[test]
+fn transmitter_matches_eh0() {
+    let recorder = Recorder::new();
+    let timing = Timing::from_dot_length(10);
+    let mut transmitter = MorseTransmitter::new(recorder.pin1(), false, timing, MESSAGE);
+    while let Some(duration) = transmitter.poll().unwrap() {
+        recorder.wait(duration);
+    }
+    assert_eq!(
+        recorder.timeline(),
+        blocking_timeline(MESSAGE, TIMING, Unsupported::Skip)
+    );
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::{blocking_timeline, MESSAGES};
+use embedded_morse::{encode, Element, Prosign, Timing, Unsupported};
+use Element::*;
+
+const DOT: u16 = 10;
+
+#[test]
+fn elements() {
+    assert_eq!(
+        encode("SOS").collect::<Vec<_>>(),
+        vec![
+            Dot, ElementGap, Dot, ElementGap, Dot, CharGap, Dash, ElementGap, Dash, ElementGap,
+            Dash, CharGap, Dot, ElementGap, Dot, ElementGap, Dot,
+        ]
+    );
+    assert_eq!(
+        encode(" e  t ").collect::<Vec<_>>(),
+        vec![Dot, WordGap, Dash]
+    );
+    assert_eq!(encode("#%").next(), None);
+}
+
+#[test]
+fn prosign_elements() {
+    assert_eq!(
+        Prosign::Ar.elements().collect::<Vec<_>>(),
+        encode("<AR>").collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn timed_matches_output() {
+    let timing = Timing::from_dot_length(DOT);
+    for message in MESSAGES {
+        let timed: Vec<_> = encode(message)
+            .timed(timing)
+            .map(|(mark, duration)| (mark, u32::from(duration)))
+            .collect();
+        let expected = blocking_timeline(message, timing, Unsupported::Skip);
+        assert_eq!(timed, expected, "{:?}", message);
+    }
+}
+
+#[test]
+fn durations() {
+    let timing = Timing::from_dot_length(DOT);
+    assert_eq!(Dot.duration(&timing), DOT);
+    assert_eq!(Dash.duration(&timing), DOT * 3);
+    assert_eq!(ElementGap.duration(&timing), DOT);
+    assert_eq!(CharGap.duration(&timing), DOT * 3);
+    assert_eq!(WordGap.duration(&timing), DOT * 7);
+    assert!(Dot.is_mark() && Dash.is_mark());
+    assert!(!ElementGap.is_mark() && !CharGap.is_mark() && !WordGap.is_mark());
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::{blocking_timeline, Recorder, TIMING};
+use embedded_morse::{encode, encoded_len, morse, Element, Encoded, Morse, Unsupported};
+
+static BEACON: Encoded<11> = morse!("VVV DE <KA>");
+
+#[test]
+fn matches_encode() {
+    assert_eq!(
+        BEACON.elements().collect::<Vec<_>>(),
+        encode("VVV DE <KA>").collect::<Vec<_>>()
+    );
+    let cases: [(&str, &[Element]); 8] = [
+        ("", &morse!("").elements().collect::<Vec<_>>()),
+        ("E", &morse!("E").elements().collect::<Vec<_>>()),
+        (" e  t ", &morse!(" e  t ").elements().collect::<Vec<_>>()),
+        ("SOS", &morse!("SOS").elements().collect::<Vec<_>>()),
+        (
+            "cq de dl1abc <AR>",
+            &morse!("cq de dl1abc <AR>").elements().collect::<Vec<_>>(),
+        ),
+        (
+            "<SOS>E<>T",
+            &morse!("<SOS>E<>T").elements().collect::<Vec<_>>(),
+        ),
+        (
+            " hello,\tworld! ",
+            &morse!(" hello,\tworld! ").elements().collect::<Vec<_>>(),
+        ),
+        (
+            "E\x0bE\x0c\r\nE",
+            &morse!("E\x0bE\x0c\r\nE").elements().collect::<Vec<_>>(),
+        ),
+    ];
+    for (message, elements) in cases {
+        assert_eq!(encode(message).collect::<Vec<_>>(), elements, "{}", message);
+    }
+}
+
+#[test]
+fn packed_length() {
+    assert_eq!(encoded_len(""), 0);
+    assert_eq!(encoded_len("E"), 1);
+    assert_eq!(encoded_len("EE"), 1);
+    assert_eq!(encoded_len("SOS"), 5);
+    assert_eq!(Encoded::<5>::new("sos"), morse!("SOS"));
+}
+
+#[test]
+#[should_panic]
+fn wrong_length() {
+    Encoded::<2>::new("SOS");
+}
+
+#[test]
+#[should_panic]
+fn unsupported() {
+    encoded_len("E#");
+}
+
+#[test]
+fn output_encoded() {
+    let recorder = Recorder::new();
+    Morse::new(recorder.delay(), recorder.pin(), false, 10)
+        .output_encoded(&BEACON)
+        .unwrap();
+    assert_eq!(
+        recorder.timeline(),
+        blocking_timeline("VVV DE <KA>", TIMING, Unsupported::Skip)
+    );
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use common::{wav, Recorder};
+use embedded_morse::{Decoder, Goertzel, Morse, ToneDetector};
+use std::f64::consts::PI;
+
+const RATE: u32 = 8000;
+const BLOCK: usize = 40;
+const DOT: u16 = 60;
+
+/// Pseudo random noise in -amplitude..=amplitude
+fn noise(amplitude: i32) -> impl FnMut() -> i32 {
+    let mut state = 0x1234_5678u32;
+    move || {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        ((state >> 16) as i32 % (2 * amplitude + 1)) - amplitude
+    }
+}
+
+fn tone(frequency: f64, amplitude: f64, samples: usize) -> Vec<i16> {
+    (0..samples)
+        .map(|i| (amplitude * (2.0 * PI * frequency * i as f64 / f64::from(RATE)).sin()) as i16)
+        .collect()
+}
+
+/// Render a message as CW audio with noise, as a WAV file
+fn render(text: &str, frequency: f64) -> Vec<u8> {
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), false, DOT);
+    morse.output_str(text).unwrap();
+    let mut timeline = recorder.timeline();
+    timeline.insert(0, (false, 300));
+    timeline.push((false, 1000));
+
+    let mut noise = noise(2000);
+    let mut samples = Vec::new();
+    for (mark, duration) in timeline {
+        let len = (duration * RATE / 1000) as usize;
+        let start = samples.len();
+        for i in start..start + len {
+            let t = i as f64 / f64::from(RATE);
+            let signal = if mark {
+                8000.0 * (2.0 * PI * frequency * t).sin()
+            } else {
+                0.0
+            };
+            samples.push((signal as i32 + noise()) as i16);
+        }
+    }
+    wav::write(&samples, RATE)
+}
+
+fn detect(wav: &[u8], frequency: u32) -> String {
+    let (rate, samples) = wav::read(wav);
+    let mut detector = ToneDetector::new(rate, frequency, 3000, 1500, Decoder::new(DOT));
+    samples
+        .chunks(BLOCK)
+        .filter_map(|block| detector.process(block))
+        .collect()
+}
+
+#[test]
+fn magnitude() {
+    let goertzel = Goertzel::new(RATE, 700);
+    let on = goertzel.magnitude(&tone(700.0, 10000.0, 400));
+    assert!((9900..=10100).contains(&on), "{}", on);
+    let off = goertzel.magnitude(&tone(1400.0, 10000.0, 400));
+    assert!(off < 200, "{}", off);
+    assert_eq!(goertzel.magnitude(&[]), 0);
+    assert_eq!(goertzel.magnitude(&[0; 100]), 0);
+}
+
+#[test]
+fn full_scale() {
+    let goertzel = Goertzel::new(RATE, 1000);
+    let magnitude = goertzel.magnitude(&tone(1000.0, 32767.0, 800));
+    assert!((32500..=33000).contains(&magnitude), "{}", magnitude);
+}
+
+#[test]
+fn decodes_audio() {
+    let wav = render("CQ DE DL1ABC", 700.0);
+    assert_eq!(detect(&wav, 700), "CQ DE DL1ABC");
+}
+
+#[test]
+fn ignores_other_tones() {
+    let wav = render("CQ DE DL1ABC", 1500.0);
+    assert_eq!(detect(&wav, 700), "");
+}
+
+#[test]
+fn hysteresis() {
+    let mut detector = ToneDetector::new(RATE, 700, 3000, 1500, Decoder::new(DOT));
+    detector.process(&tone(700.0, 2000.0, BLOCK));
+    assert!(!detector.tone());
+    detector.process(&tone(700.0, 4000.0, BLOCK));
+    assert!(detector.tone());
+    detector.process(&tone(700.0, 2000.0, BLOCK));
+    assert!(detector.tone());
+    detector.process(&tone(700.0, 1000.0, BLOCK));
+    assert!(!detector.tone());
+    assert_eq!(detector.decoder().dot_length(), DOT);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::CODES;
+use core::fmt::Write;
+use embedded_morse::{Notation, Prosign};
+
+fn render(message: &str) -> String {
+    Notation::STANDARD.render(message).to_string()
+}
+
+fn parse(notation: &str) -> String {
+    Notation::STANDARD.parse(notation).collect()
+}
+
+#[test]
+fn every_character() {
+    for &(c, code) in CODES {
+        assert_eq!(render(&c.to_string()), code, "{:?}", c);
+        assert_eq!(parse(code), c.to_string(), "{:?}", code);
+    }
+}
+
+#[test]
+fn words() {
+    assert_eq!(render("SOS"), "... --- ...");
+    assert_eq!(render(" a  b "), ".- / -...");
+    assert_eq!(render("E<AR>E"), ". .-.-. .");
+    assert_eq!(render("#"), "");
+    assert_eq!(parse(".- / -..."), "A B");
+}
+
+#[test]
+fn prosign() {
+    let rendered = Notation::STANDARD.render_elements(Prosign::Sos.elements());
+    assert_eq!(rendered.to_string(), "...---...");
+}
+
+#[test]
+fn round_trip() {
+    let message = "CQ DE DL1ABC, 73!";
+    assert_eq!(parse(&render(message)), message);
+}
+
+#[test]
+fn parse_is_lenient() {
+    assert_eq!(parse("  ...   --- / ... "), "SO S");
+    assert_eq!(parse(" / ... / "), "S");
+    assert_eq!(parse(""), "");
+}
+
+#[test]
+fn unknown_patterns() {
+    assert_eq!(
+        parse("........- ..x -----------------"),
+        "\u{FFFD}\u{FFFD}\u{FFFD}"
+    );
+}
+
+#[test]
+fn custom_notation() {
+    let notation = Notation {
+        dot: '·',
+        dash: '−',
+        char_separator: "|",
+        word_separator: "  ",
+    };
+    let mut text = String::new();
+    write!(text, "{}", notation.render("TEST IT")).unwrap();
+    assert_eq!(text, "−|·|···|−  ··|−");
+    assert_eq!(notation.parse(&text).collect::<String>(), "TEST IT");
+}
+
+#[test]
+fn default_is_standard() {
+    assert_eq!(Notation::default(), Notation::STANDARD);
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use common::{blocking_timeline, Event, Recorder, CODES, TIMING};
+use embedded_morse::{Morse, Timing, Unsupported};
+
+const DOT: u32 = 10;
+
+fn code(c: char) -> &'static str {
+    CODES
+        .iter()
+        .find(|(ch, _)| *ch == c.to_ascii_uppercase())
+        .map(|(_, code)| *code)
+        .unwrap()
+}
+
+/// Push a segment, merging it with the previous one if the level matches
+fn push(timeline: &mut Vec<(bool, u32)>, level: bool, duration: u32) {
+    match timeline.last_mut() {
+        Some((last, d)) if *last == level => *d += duration,
+        _ => timeline.push((level, duration)),
+    }
+}
+
+/// Build the expected timeline for a string of supported characters and spaces
+fn expected(text: &str) -> Vec<(bool, u32)> {
+    let mut timeline = Vec::new();
+    for (i, word) in text.split_whitespace().enumerate() {
+        if i != 0 {
+            push(&mut timeline, false, DOT * 7);
+        }
+        for (j, c) in word.chars().enumerate() {
+            if j != 0 {
+                push(&mut timeline, false, DOT * 3);
+            }
+            for (k, element) in code(c).chars().enumerate() {
+                if k != 0 {
+                    push(&mut timeline, false, DOT);
+                }
+                let length = if element == '-' { DOT * 3 } else { DOT };
+                push(&mut timeline, true, length);
+            }
+        }
+    }
+    timeline
+}
+
+#[test]
+fn every_character() {
+    for &(c, _) in CODES {
+        let text = c.to_string();
+        assert_eq!(
+            blocking_timeline(&text, TIMING, Unsupported::Skip),
+            expected(&text),
+            "character {:?}",
+            c
+        );
+    }
+}
+
+#[test]
+fn lowercase_matches_uppercase() {
+    for c in 'a'..='z' {
+        let text = c.to_string();
+        assert_eq!(
+            blocking_timeline(&text, TIMING, Unsupported::Skip),
+            expected(&text),
+            "character {:?}",
+            c
+        );
+    }
+}
+
+#[test]
+fn words() {
+    assert_eq!(
+        blocking_timeline("SOS DL1ABC", TIMING, Unsupported::Skip),
+        expected("SOS DL1ABC")
+    );
+}
+
+#[test]
+fn gaps() {
+    assert_eq!(
+        blocking_timeline("EE E", TIMING, Unsupported::Skip),
+        vec![
+            (true, DOT),
+            (false, DOT * 3),
+            (true, DOT),
+            (false, DOT * 7),
+            (true, DOT)
+        ]
+    );
+}
+
+#[test]
+fn whitespace_only_separates_words() {
+    assert_eq!(
+        blocking_timeline("  SOS \t\n SOS  ", TIMING, Unsupported::Skip),
+        expected("SOS SOS")
+    );
+    assert!(blocking_timeline(" ", TIMING, Unsupported::Skip).is_empty());
+}
+
+#[test]
+fn consecutive_messages() {
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), false, DOT as u16);
+    for message in ["E", "", "E", "SOS"] {
+        morse.output_str(message).unwrap();
+    }
+    assert_eq!(recorder.timeline(), expected("EESOS"));
+}
+
+#[test]
+fn whitespace_between_messages() {
+    let cases: [(&[&str], &str); 5] = [
+        (&["CQ", " DE"], "CQ DE"),
+        (&["CQ ", "DE"], "CQ DE"),
+        (&["E", " ", "E"], "E E"),
+        (&["E", "", " \t", "", "E"], "E E"),
+        (&[" ", "E", " "], "E"),
+    ];
+    for (messages, text) in cases {
+        let recorder = Recorder::new();
+        let mut morse = Morse::new(recorder.delay(), recorder.pin(), false, DOT as u16);
+        for message in messages {
+            morse.output_str(message).unwrap();
+        }
+        assert_eq!(recorder.timeline(), expected(text), "{:?}", messages);
+    }
+}
+
+#[test]
+fn paris_is_fifty_units() {
+    // One word including the following word gap
+    let total: u32 = blocking_timeline("PARIS PARIS", TIMING, Unsupported::Skip)
+        .iter()
+        .map(|(_, duration)| duration)
+        .sum();
+    let last_word: u32 = blocking_timeline("PARIS", TIMING, Unsupported::Skip)
+        .iter()
+        .map(|(_, duration)| duration)
+        .sum();
+    assert_eq!(total - last_word, DOT * 50);
+}
+
+#[test]
+fn unsupported_characters_are_skipped() {
+    assert_eq!(
+        blocking_timeline("E#%E\u{e4}", TIMING, Unsupported::Skip),
+        expected("EE")
+    );
+}
+
+#[test]
+fn pin_ends_inactive() {
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), false, DOT as u16);
+    morse.output_str("PARIS").unwrap();
+    let events = recorder.events();
+    let last_level = events.iter().rev().find(|e| !matches!(e, Event::Delay(_)));
+    assert_eq!(last_level, Some(&Event::Low));
+}
+
+#[test]
+fn inverted_output() {
+    let normal = blocking_timeline("K", TIMING, Unsupported::Skip);
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), true, DOT as u16);
+    morse.output_str("K").unwrap();
+    let inverted: Vec<_> = recorder
+        .timeline()
+        .into_iter()
+        .map(|(level, duration)| (!level, duration))
+        .collect();
+    assert_eq!(inverted, normal);
+}
+
+#[test]
+fn custom_timing() {
+    let timing = Timing::from_dot_length(20);
+    assert_eq!(
+        (
+            timing.dot(),
+            timing.dash(),
+            timing.element_gap(),
+            timing.char_gap(),
+            timing.word_gap()
+        ),
+        (20, 60, 20, 60, 140)
+    );
+
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), false, 1);
+    morse.set_timing(timing);
+    assert_eq!(morse.timing(), timing);
+    morse.output_str("A").unwrap();
+    assert_eq!(
+        recorder.timeline(),
+        vec![(true, 20), (false, 20), (true, 60)]
+    );
+}
+
+#[test]
+fn default_dot_length() {
+    let recorder = Recorder::new();
+    let mut morse = Morse::new_default(recorder.delay(), recorder.pin(), false);
+    morse.output_str("E").unwrap();
+    assert_eq!(recorder.timeline(), vec![(true, 300)]);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::{blocking_timeline, input, sample};
+use embedded_morse::{Decoder, PinDecoder, ReferenceWord, Timing, Unsupported};
+
+const DOT: u16 = 50;
+const PERIOD: u16 = 5;
+
+fn encode(text: &str, wpm: u16) -> Vec<(bool, u32)> {
+    let timing = Timing::from_wpm(wpm, ReferenceWord::Paris);
+    let mut timeline = blocking_timeline(text, timing, Unsupported::Skip);
+    // Idle before and after the message
+    timeline.insert(0, (false, 500));
+    timeline.push((false, 2000));
+    timeline
+}
+
+fn receive(pin_decoder: &mut PinDecoder<common::Input>, samples: usize) -> String {
+    (0..samples)
+        .filter_map(|_| pin_decoder.poll().unwrap())
+        .collect()
+}
+
+#[test]
+fn decodes_samples() {
+    let samples = sample(&encode("CQ DE DL1ABC", 24), u32::from(PERIOD));
+    let len = samples.len();
+    let mut pin_decoder = PinDecoder::new(input(samples), false, PERIOD, Decoder::new(DOT));
+    assert_eq!(receive(&mut pin_decoder, len), "CQ DE DL1ABC");
+}
+
+#[test]
+fn inverted_input() {
+    let samples: Vec<_> = sample(&encode("SOS", 24), u32::from(PERIOD))
+        .into_iter()
+        .map(|level| !level)
+        .collect();
+    let len = samples.len();
+    let mut pin_decoder = PinDecoder::new(input(samples), true, PERIOD, Decoder::new(DOT));
+    assert_eq!(receive(&mut pin_decoder, len), "SOS");
+}
+
+#[test]
+fn debounces_glitches() {
+    let mut samples = sample(&encode("PARIS", 24), u32::from(PERIOD));
+    for i in (3..samples.len()).step_by(7) {
+        samples[i] = !samples[i];
+    }
+    let len = samples.len();
+
+    let mut pin_decoder = PinDecoder::new(input(samples.clone()), false, PERIOD, Decoder::new(DOT));
+    assert_eq!(receive(&mut pin_decoder, len), "PARIS");
+
+    let mut pin_decoder = PinDecoder::new(input(samples), false, PERIOD, Decoder::new(DOT));
+    pin_decoder.set_debounce(1);
+    assert_ne!(receive(&mut pin_decoder, len), "PARIS");
+}
+
+#[test]
+fn last_character_after_word_gap() {
+    let samples = sample(&encode("E", 24), u32::from(PERIOD));
+    let mut pin_decoder = PinDecoder::new(input(samples), false, PERIOD, Decoder::new(DOT));
+    // Leading idle, the dot and almost a word gap
+    assert_eq!(receive(&mut pin_decoder, (500 + 50 + 340) / 5), "");
+    assert_eq!(receive(&mut pin_decoder, 2), "E");
+    assert_eq!(receive(&mut pin_decoder, 1000), "");
+}
+
+#[test]
+fn adaptive() {
+    let samples = sample(&encode("THE QUICK BROWN FOX", 16), u32::from(PERIOD));
+    let len = samples.len();
+    let mut pin_decoder = PinDecoder::new(input(samples), false, PERIOD, Decoder::adaptive(DOT));
+    assert_eq!(receive(&mut pin_decoder, len), "THE QUICK BROWN FOX");
+    assert_eq!(pin_decoder.decoder().wpm(ReferenceWord::Paris), 16);
+    pin_decoder.free();
+}
+
+#[test]
+fn long_idle() {
+    // Longer than u32::MAX ms
+    let polls = 70_000;
+    let mut pin_decoder = PinDecoder::new(
+        input(vec![false; polls]),
+        false,
+        u16::MAX,
+        Decoder::new(DOT),
+    );
+    assert_eq!(receive(&mut pin_decoder, polls), "");
+}
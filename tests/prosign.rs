@@ -0,0 +1,98 @@
+mod common;
+
+use common::{run_timeline, Recorder};
+use embedded_morse::{Morse, PinSink, Prosign};
+
+const DOT: u32 = 10;
+
+const PROSIGNS: &[(Prosign, &str)] = &[
+    (Prosign::Ar, ".-.-."),
+    (Prosign::As, ".-..."),
+    (Prosign::Bk, "-...-.-"),
+    (Prosign::Bt, "-...-"),
+    (Prosign::Cl, "-.-..-.."),
+    (Prosign::Ka, "-.-.-"),
+    (Prosign::Kn, "-.--."),
+    (Prosign::Sk, "...-.-"),
+    (Prosign::Sn, "...-."),
+    (Prosign::Sos, "...---..."),
+    (Prosign::Error, "........"),
+];
+
+fn morse(recorder: &Recorder) -> Morse<common::Delay, PinSink<common::Pin, common::Hal>> {
+    Morse::new(recorder.delay(), recorder.pin(), false, DOT as u16)
+}
+
+#[test]
+fn prosign_then_text() {
+    let recorder = Recorder::new();
+    let mut morse = morse(&recorder);
+    morse.output_prosign(Prosign::Ka).unwrap();
+    morse.output_str("T").unwrap();
+    let mut expected = run_timeline("-.-.-", DOT);
+    expected.push((false, DOT * 3));
+    expected.push((true, DOT * 3));
+    assert_eq!(recorder.timeline(), expected);
+}
+
+#[test]
+fn output_prosign() {
+    for &(prosign, code) in PROSIGNS {
+        let recorder = Recorder::new();
+        morse(&recorder).output_prosign(prosign).unwrap();
+        assert_eq!(
+            recorder.timeline(),
+            run_timeline(code, DOT),
+            "{:?}",
+            prosign
+        );
+    }
+}
+
+#[test]
+fn escape_syntax_matches_prosign() {
+    for &(prosign, code) in PROSIGNS {
+        let recorder = Recorder::new();
+        let text = format!("<{}>", prosign.letters().to_lowercase());
+        morse(&recorder).output_str(&text).unwrap();
+        assert_eq!(
+            recorder.timeline(),
+            run_timeline(code, DOT),
+            "{:?}",
+            prosign
+        );
+    }
+}
+
+#[test]
+fn gaps_around_prosign() {
+    let recorder = Recorder::new();
+    morse(&recorder).output_str("E<AR>E <BT>").unwrap();
+    let mut expected = run_timeline(".", DOT);
+    expected.push((false, DOT * 3));
+    expected.extend(run_timeline(".-.-.", DOT));
+    expected.push((false, DOT * 3));
+    expected.extend(run_timeline(".", DOT));
+    expected.push((false, DOT * 7));
+    expected.extend(run_timeline("-...-", DOT));
+    assert_eq!(recorder.timeline(), expected);
+}
+
+#[test]
+fn empty_prosign_keeps_word_gap() {
+    let recorder = Recorder::new();
+    morse(&recorder).output_str("E <> E").unwrap();
+    let mut expected = run_timeline(".", DOT);
+    expected.push((false, DOT * 7));
+    expected.extend(run_timeline(".", DOT));
+    assert_eq!(recorder.timeline(), expected);
+}
+
+#[test]
+fn unterminated_bracket_is_skipped() {
+    let unterminated = Recorder::new();
+    morse(&unterminated).output_str("<AR").unwrap();
+    let plain = Recorder::new();
+    morse(&plain).output_str("AR").unwrap();
+    assert_eq!(unterminated.timeline(), plain.timeline());
+}
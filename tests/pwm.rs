@@ -0,0 +1,101 @@
+mod common;
+
+use common::{blocking_timeline, Event, Recorder, TIMING};
+use embedded_morse::{Morse, PwmTone, Timing, Unsupported};
+
+const MESSAGE: &str = "CQ DE DL1ABC";
+
+fn duties(recorder: &Recorder) -> Vec<u16> {
+    let mut duties: Vec<_> = recorder
+        .events()
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::Duty(duty) => Some(duty),
+            _ => None,
+        })
+        .collect();
+    duties.sort_unstable();
+    duties.dedup();
+    duties
+}
+
+#[cfg(feature = "eh0")]
+#[test]
+fn eh0_pwm() {
+    let recorder = Recorder::new();
+    let tone = PwmTone::new(recorder.pwm(1000), 50);
+    let mut morse = Morse::with_sink(recorder.delay(), tone, Timing::from_dot_length(10));
+    morse.output_str(MESSAGE).unwrap();
+    assert_eq!(
+        recorder.timeline(),
+        blocking_timeline(MESSAGE, TIMING, Unsupported::Skip)
+    );
+    assert_eq!(duties(&recorder), vec![0, 500]);
+}
+
+#[cfg(feature = "eh0")]
+#[test]
+fn eh0_pwm_channel() {
+    use embedded_morse::PwmChannel;
+
+    let recorder = Recorder::new();
+    let channel = PwmChannel::new(recorder.pwm_timer(1000), 1);
+    let set_period: fn(&mut PwmChannel<_>, u32) = |channel, hz| {
+        eh0::Pwm::set_period(&mut channel.pwm, hz);
+    };
+    let mut tone = PwmTone::with_frequency(channel, 50, 700, set_period);
+    assert_eq!(tone.frequency(), Some(700));
+    assert_eq!(recorder.events(), vec![Event::Frequency(700)]);
+    tone.set_frequency(650);
+    assert_eq!(tone.frequency(), Some(650));
+    let mut morse = Morse::with_sink(recorder.delay(), tone, Timing::from_dot_length(10));
+    morse.output_str(MESSAGE).unwrap();
+    assert_eq!(
+        recorder.timeline(),
+        blocking_timeline(MESSAGE, TIMING, Unsupported::Skip)
+    );
+    assert_eq!(duties(&recorder), vec![0, 500]);
+}
+
+#[cfg(feature = "eh1")]
+#[test]
+fn eh1_frequency() {
+    let recorder = Recorder::new();
+    let mut tone = PwmTone::new(recorder.pwm1(255), 20);
+    tone.set_frequency(800);
+    assert_eq!(tone.frequency(), None);
+    let mut tone = PwmTone::with_frequency(recorder.pwm1(255), 20, 800, |_, hz| {
+        assert_eq!(hz, 800);
+    });
+    assert_eq!(tone.frequency(), Some(800));
+    tone.set_frequency(800);
+}
+
+#[cfg(feature = "eh1")]
+#[test]
+fn eh1_pwm() {
+    let recorder = Recorder::new();
+    let tone = PwmTone::new(recorder.pwm1(255), 20);
+    let mut morse = Morse::with_sink(recorder.delay1(), tone, Timing::from_dot_length(10));
+    morse.output_str(MESSAGE).unwrap();
+    assert_eq!(
+        recorder.timeline(),
+        blocking_timeline(MESSAGE, TIMING, Unsupported::Skip)
+    );
+    assert_eq!(duties(&recorder), vec![0, 51]);
+}
+
+#[cfg(feature = "eh0")]
+#[test]
+fn duty_is_limited() {
+    use embedded_morse::KeySink;
+
+    let recorder = Recorder::new();
+    let mut tone = PwmTone::new(recorder.pwm(400), 150);
+    tone.key_down().unwrap();
+    tone.set_duty_percent(25);
+    tone.key_down().unwrap();
+    tone.key_up().unwrap();
+    assert_eq!(duties(&recorder), vec![0, 100, 400]);
+    tone.free();
+}
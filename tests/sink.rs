@@ -0,0 +1,91 @@
+mod common;
+
+use common::{blocking_timeline, Event, Recorder, MESSAGES, TIMING};
+use embedded_morse::{
+    KeySink, Morse, MorseTransmitter, PinSink, RecordingSink, Timing, Unsupported,
+};
+
+const DOT: u16 = 10;
+
+#[test]
+fn recording_matches_pin() {
+    for message in MESSAGES {
+        let recorder = Recorder::new();
+        let mut recording = RecordingSink::<128>::new();
+        let timing = Timing::from_dot_length(DOT);
+        let mut morse = Morse::with_sink(recorder.delay(), &mut recording, timing);
+        morse.output_str(message).unwrap();
+        assert_eq!(
+            recording.timeline(),
+            &blocking_timeline(message, TIMING, Unsupported::Skip)[..],
+            "{:?}",
+            message
+        );
+        assert!(!recording.is_overflowed());
+    }
+}
+
+#[test]
+fn recording_transmitter() {
+    let mut recording = RecordingSink::<128>::new();
+    let timing = Timing::from_dot_length(DOT);
+    let mut transmitter = MorseTransmitter::with_sink(&mut recording, timing, "CQ DE DL1ABC");
+    while transmitter.poll().unwrap().is_some() {}
+    assert_eq!(
+        recording.timeline(),
+        &blocking_timeline("CQ DE DL1ABC", TIMING, Unsupported::Skip)[..]
+    );
+}
+
+#[test]
+fn recording_overflow() {
+    let recorder = Recorder::new();
+    let timing = Timing::from_dot_length(DOT);
+    let mut morse = Morse::with_sink(recorder.delay(), RecordingSink::<4>::new(), timing);
+    morse.output_str("SOS").unwrap();
+    let (_, mut recording) = morse.free();
+    assert_eq!(
+        recording.timeline(),
+        &blocking_timeline("SOS", TIMING, Unsupported::Skip)[..4]
+    );
+    assert!(recording.is_overflowed());
+    recording.clear();
+    assert_eq!(recording.timeline(), &[]);
+    assert!(!recording.is_overflowed());
+}
+
+#[test]
+fn several_pins() {
+    let recorder = Recorder::new();
+    let pins = [
+        PinSink::new(recorder.pin(), false),
+        PinSink::new(recorder.pin(), false),
+    ];
+    let mut morse = Morse::with_sink(recorder.delay(), pins, Timing::from_dot_length(DOT));
+    morse.output_str("E").unwrap();
+    assert_eq!(
+        recorder.events(),
+        vec![
+            Event::High,
+            Event::High,
+            Event::Delay(DOT),
+            Event::Low,
+            Event::Low
+        ]
+    );
+}
+
+#[test]
+fn pin_and_inverted_pin() {
+    let recorder = Recorder::new();
+    let mut sink = (
+        PinSink::new(recorder.pin(), false),
+        PinSink::new(recorder.pin(), true),
+    );
+    sink.key_down().unwrap();
+    sink.key_up().unwrap();
+    assert_eq!(
+        recorder.events(),
+        vec![Event::High, Event::Low, Event::Low, Event::High]
+    );
+}
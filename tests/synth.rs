@@ -0,0 +1,95 @@
+mod common;
+
+use common::{blocking_timeline, wav};
+use embedded_morse::{
+    encode, Decoder, Goertzel, Prosign, Synth, Timing, ToneDetector, Unsupported,
+};
+
+const RATE: u32 = 8000;
+const MESSAGE: &str = "CQ DE DL1ABC <AR>";
+
+fn timing() -> Timing {
+    Timing::from_dot_length(60)
+}
+
+#[test]
+fn length_matches_timing() {
+    let total: u32 = blocking_timeline(MESSAGE, timing(), Unsupported::Skip)
+        .iter()
+        .map(|(_, d)| d)
+        .sum();
+    let samples = Synth::new(RATE, 700).render(MESSAGE, timing()).count();
+    assert_eq!(samples as u32, total * RATE / 1000);
+    assert_eq!(Synth::new(RATE, 700).render("", timing()).count(), 0);
+}
+
+#[test]
+fn marks_and_gaps() {
+    let synth = Synth::new(RATE, 700);
+    let samples: Vec<_> = synth.render(MESSAGE, timing()).collect();
+    let goertzel = Goertzel::new(RATE, 700);
+    let mut position = 0;
+    for (mark, duration) in blocking_timeline(MESSAGE, timing(), Unsupported::Skip) {
+        let len = (duration * RATE / 1000) as usize;
+        let element = &samples[position..position + len];
+        if mark {
+            // Full amplitude apart from the ramps
+            let magnitude = goertzel.magnitude(&element[len / 4..len * 3 / 4]);
+            assert!(magnitude > 15000, "{}", magnitude);
+            assert!(element[0].abs() < 100 && element[len - 1].abs() < 500);
+        } else {
+            assert!(element.iter().all(|&sample| sample == 0));
+        }
+        position += len;
+    }
+}
+
+#[test]
+fn no_key_clicks() {
+    let mut synth = Synth::new(RATE, 600);
+    synth.amplitude = i16::MAX;
+    synth.ramp = 8;
+    let samples: Vec<_> = synth.render("PARIS", timing()).collect();
+    // The steepest slope of the sine itself, plus some rounding
+    let max_step = f64::from(i16::MAX) * 2.0 * std::f64::consts::PI * 600.0 / f64::from(RATE);
+    for pair in samples.windows(2) {
+        let step = (i32::from(pair[1]) - i32::from(pair[0])).abs();
+        assert!(f64::from(step) <= max_step + 10.0, "{}", step);
+    }
+}
+
+#[test]
+fn wav_round_trip() {
+    let synth = Synth::new(RATE, 700);
+    let samples: Vec<_> = synth.render(MESSAGE, timing()).collect();
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("synth.wav");
+    std::fs::write(&path, wav::write(&samples, RATE)).unwrap();
+
+    let (rate, mut samples) = wav::read(&std::fs::read(&path).unwrap());
+    samples.extend(std::iter::repeat_n(0, rate as usize));
+    let mut detector = ToneDetector::new(rate, 700, 3000, 1500, Decoder::new(60));
+    let text: String = samples
+        .chunks(40)
+        .filter_map(|block| detector.process(block))
+        .collect();
+    // The prosign decodes as '+', which has the same code
+    assert_eq!(text, "CQ DE DL1ABC +");
+}
+
+#[test]
+fn prosign() {
+    let synth = Synth::new(RATE, 700);
+    let samples = synth.render_prosign(Prosign::Sos, timing()).count();
+    // 3 dots, 3 dashes, 3 dots and 8 element gaps
+    assert_eq!(samples as u32, (6 + 9 + 8) * 60 * RATE / 1000);
+}
+
+#[test]
+fn elements() {
+    static TABLE: [(char, &str); 1] = [('#', "...---...")];
+    let synth = Synth::new(RATE, 700);
+    let elements = encode("#").with_table(&TABLE);
+    assert!(synth
+        .render_elements(elements, timing())
+        .eq(synth.render_prosign(Prosign::Sos, timing())));
+}
@@ -0,0 +1,286 @@
+mod common;
+
+use common::{blocking_timeline, Recorder, TIMING};
+use embedded_morse::{encode, CodeTable, Elements, Morse, MorseChar, Notation, Table, Unsupported};
+
+fn render(elements: Elements) -> String {
+    Notation::STANDARD.render_elements(elements).to_string()
+}
+
+static TABLE: Table = Table::new(&[('Ä', MorseChar::new(".-.-")), ('Æ', MorseChar::new(".-.-"))])
+    .with_expansions(&[('ß', "SS")]);
+
+#[test]
+fn custom_table() {
+    assert_eq!(
+        render(encode("Äß ä").with_table(&TABLE)),
+        ".-.- ... ... / .-.-"
+    );
+    assert_eq!(render(encode("Äß ä")), "");
+    assert_eq!(TABLE.code('æ'), Some(MorseChar::new(".-.-")));
+    assert_eq!(TABLE.char(MorseChar::new(".-.-")), Some('Ä'));
+    assert_eq!(TABLE.char(MorseChar::new("...")), Some('S'));
+    assert_eq!(TABLE.code('1'), Some(MorseChar::new(".----")));
+}
+
+#[test]
+fn morse_uses_table() {
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), false, 10);
+    morse.set_table(&TABLE);
+    morse.output_str("ä").unwrap();
+    assert_eq!(
+        recorder.timeline(),
+        blocking_timeline("<AA>", TIMING, Unsupported::Skip)
+    );
+}
+
+#[test]
+fn unsupported_with_table() {
+    assert_eq!(encode("Äß").with_table(&TABLE).find_unsupported(), None);
+    assert_eq!(encode("Äß").find_unsupported(), Some((0, 'Ä')));
+    assert_eq!(
+        encode("ÄØ").with_table(&TABLE).find_unsupported(),
+        Some((2, 'Ø'))
+    );
+}
+
+#[test]
+#[should_panic]
+fn invalid_code() {
+    MorseChar::new(".x");
+}
+
+#[test]
+fn parse_code() {
+    assert_eq!(MorseChar::parse(".-"), Some(MorseChar::new(".-")));
+    assert_eq!(MorseChar::parse(".. ."), Some(MorseChar::new(".. .")));
+    assert_eq!(MorseChar::parse(""), None);
+    assert_eq!(MorseChar::parse(".x"), None);
+    assert_eq!(MorseChar::parse(". "), None);
+    assert_eq!(MorseChar::parse("-----------------"), None);
+    assert!(MorseChar::parse("................").is_some());
+    assert!(MorseChar::parse("..._....").is_some());
+    assert_eq!(MorseChar::parse("..._....."), None);
+    assert_eq!(MorseChar::parse(". . . . . . . . ."), None);
+    assert_ne!(MorseChar::new(".-"), MorseChar::new(". -"));
+    assert_eq!(core::mem::size_of::<MorseChar>(), 4);
+}
+
+static CODES: [(char, &str); 3] = [('#', "...-.-"), ('€', ".x"), ('A', "-")];
+
+#[test]
+fn code_array() {
+    assert_eq!(
+        render(encode("#A €B").with_table(&CODES)),
+        "...-.- - / -..."
+    );
+    assert_eq!(CODES.code('a'), Some(MorseChar::new("-")));
+    assert_eq!(CODES.char(MorseChar::new("-")), Some('A'));
+    assert_eq!(CODES.char(MorseChar::new(".-")), Some('A'));
+    assert_eq!(CODES.char(MorseChar::new("...-.-")), Some('#'));
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), false, 10);
+    morse.set_table(&CODES);
+    morse.output_str("#").unwrap();
+    assert_eq!(
+        recorder.timeline(),
+        blocking_timeline("<SK>", TIMING, Unsupported::Skip)
+    );
+}
+
+/// Cut numbers, with codes chosen when looking them up
+struct CutNumbers(core::sync::atomic::AtomicBool);
+
+impl CodeTable for CutNumbers {
+    fn code(&self, c: char) -> Option<MorseChar> {
+        match c {
+            '0' if self.0.load(core::sync::atomic::Ordering::Relaxed) => Some(MorseChar::new("-")),
+            '9' => Some(MorseChar::new("-.")),
+            c => embedded_morse::International.code(c),
+        }
+    }
+
+    fn char(&self, code: MorseChar) -> Option<char> {
+        embedded_morse::International.char(code)
+    }
+}
+
+static CUT: CutNumbers = CutNumbers(core::sync::atomic::AtomicBool::new(false));
+
+#[test]
+fn custom_code_table() {
+    assert_eq!(render(encode("509").with_table(&CUT)), "..... ----- -.");
+    CUT.0.store(true, core::sync::atomic::Ordering::Relaxed);
+    assert_eq!(render(encode("509").with_table(&CUT)), "..... - -.");
+}
+
+#[cfg(feature = "cyrillic")]
+#[test]
+fn cyrillic() {
+    use embedded_morse::tables::CYRILLIC;
+    assert_eq!(
+        render(encode("Привет, мир 73").with_table(&CYRILLIC)),
+        ".--. .-. .. .-- . - --..-- / -- .. .-. / --... ...--"
+    );
+    assert_eq!(CYRILLIC.char(MorseChar::new("---.")), Some('Ч'));
+    assert_eq!(encode("ёЁ").with_table(&CYRILLIC).find_unsupported(), None);
+}
+
+#[cfg(feature = "greek")]
+#[test]
+fn greek() {
+    use embedded_morse::tables::GREEK;
+    assert_eq!(
+        render(encode("Καλημέρα").with_table(&GREEK)),
+        "-.- .- .-.. .... -- . .-. .-"
+    );
+    assert_eq!(render(encode("ψς").with_table(&GREEK)), "--.- ...");
+}
+
+#[cfg(feature = "hebrew")]
+#[test]
+fn hebrew() {
+    use embedded_morse::tables::HEBREW;
+    assert_eq!(render(encode("שלום").with_table(&HEBREW)), "... .-.. . --");
+}
+
+#[cfg(feature = "arabic")]
+#[test]
+fn arabic() {
+    use embedded_morse::tables::ARABIC;
+    assert_eq!(render(encode("سلام").with_table(&ARABIC)), "... .-.. .- --");
+}
+
+#[cfg(feature = "wabun")]
+#[test]
+fn wabun() {
+    use embedded_morse::tables::Wabun;
+    assert_eq!(
+        render(encode("モールス").with_table(&Wabun)),
+        "-..-. .--.- -.--. ---.-"
+    );
+    assert_eq!(
+        render(encode("もーるす").with_table(&Wabun)),
+        render(encode("モールス").with_table(&Wabun))
+    );
+    assert_eq!(render(encode("ガ").with_table(&Wabun)), ".-.. ..");
+    assert_eq!(render(encode("<ガ>").with_table(&Wabun)), ".-....");
+    assert_eq!(encode("ばぱ").with_table(&Wabun).find_unsupported(), None);
+    assert_eq!(Wabun.char(MorseChar::new("-.-.-")), Some('サ'));
+}
+
+#[cfg(feature = "latin-extended")]
+#[test]
+fn latin_extended() {
+    use embedded_morse::tables::LATIN_EXTENDED;
+    assert_eq!(
+        render(encode("Grüße, Ça").with_table(&LATIN_EXTENDED)),
+        "--. .-. ..-- . --..-- / -.-.. .-"
+    );
+    assert_eq!(
+        render(encode("Äö Ñ Þ ŭ ð").with_table(&LATIN_EXTENDED)),
+        ".-.- ---. / --.-- / .--.. / ..-- / ..--."
+    );
+    assert_eq!(LATIN_EXTENDED.char(MorseChar::new("..-..")), Some('É'));
+    assert_eq!(LATIN_EXTENDED.char(MorseChar::new(".-.-")), Some('Ä'));
+    assert_eq!(LATIN_EXTENDED.char(MorseChar::new("..--")), Some('Ü'));
+    assert_eq!(LATIN_EXTENDED.char(MorseChar::new("..--.")), Some('Ð'));
+}
+
+#[cfg(feature = "latin-extended")]
+#[test]
+fn latin_extended_ch() {
+    use embedded_morse::tables::{LATIN_EXTENDED, LATIN_EXTENDED_CH};
+    assert_eq!(
+        render(encode("Nacht <CH>c").with_table(&LATIN_EXTENDED_CH)),
+        "-. .- ---- - / ---- -.-."
+    );
+    assert_eq!(
+        render(encode("Nacht").with_table(&LATIN_EXTENDED)),
+        "-. .- -.-. .... -"
+    );
+    assert_eq!(
+        LATIN_EXTENDED_CH.digraph('c', 'h'),
+        Some(MorseChar::new("----"))
+    );
+    assert_eq!(LATIN_EXTENDED_CH.code('Ü'), Some(MorseChar::new("..--")));
+    assert_eq!(LATIN_EXTENDED_CH.char(MorseChar::new("----")), Some('Ĥ'));
+}
+
+#[cfg(feature = "latin-extended")]
+#[test]
+fn latin_transliterated() {
+    use embedded_morse::tables::LATIN_TRANSLITERATED;
+    let transliterated = encode("Grüße, Þór").with_table(&LATIN_TRANSLITERATED);
+    assert_eq!(transliterated.find_unsupported(), Some((11, 'ó')));
+    assert_eq!(render(transliterated), render(encode("GRUESSE, THr")));
+}
+
+#[test]
+#[should_panic]
+fn leading_internal_space() {
+    MorseChar::new(" ..");
+}
+
+#[test]
+#[should_panic]
+fn double_internal_space() {
+    MorseChar::new(".  .");
+}
+
+#[test]
+fn long_elements() {
+    use embedded_morse::{Element, Timing, Weighting};
+    let timing = Timing::from_dot_length(10);
+    assert_eq!(Element::LongDash.duration(&timing), 50);
+    assert_eq!(Element::ExtraLongDash.duration(&timing), 60);
+    assert_eq!(Element::InternalGap.duration(&timing), 20);
+    let timing = timing.with_weighting(Weighting::AMERICAN);
+    assert_eq!(Element::Dash.duration(&timing), 20);
+    assert_eq!(Element::LongDash.duration(&timing), 40);
+    assert_eq!(Element::ExtraLongDash.duration(&timing), 50);
+    assert!(Element::LongDash.is_mark() && Element::ExtraLongDash.is_mark());
+    assert!(!Element::InternalGap.is_mark());
+}
+
+#[cfg(feature = "american")]
+#[test]
+fn american() {
+    use embedded_morse::tables::AMERICAN;
+    use embedded_morse::{Timing, Weighting};
+    assert_eq!(
+        render(encode("Cool 10").with_table(&AMERICAN)),
+        ".. . . . . . ⸺ / .--. ⸻"
+    );
+    assert_eq!(
+        encode("Ä").with_table(&AMERICAN).find_unsupported(),
+        Some((0, 'Ä'))
+    );
+    assert_eq!(
+        encode("@").with_table(&AMERICAN).find_unsupported(),
+        Some((0, '@'))
+    );
+    assert_eq!(AMERICAN.char(MorseChar::new(". ..")), Some('R'));
+    assert_eq!(AMERICAN.char(MorseChar::new("...-..-")), None);
+
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), false, 10);
+    morse.set_table(&AMERICAN);
+    morse.set_timing(Timing::from_dot_length(10).with_weighting(Weighting::AMERICAN));
+    morse.output_str("CL0").unwrap();
+    assert_eq!(
+        recorder.timeline(),
+        vec![
+            (true, 10),
+            (false, 10),
+            (true, 10),
+            (false, 20),
+            (true, 10),
+            (false, 30),
+            (true, 40),
+            (false, 30),
+            (true, 50),
+        ]
+    );
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use common::Recorder;
+use embedded_morse::{Morse, ReferenceWord, Timing, Weighting};
+
+fn duration(text: &str, timing: Timing) -> u32 {
+    let recorder = Recorder::new();
+    let mut morse = Morse::with_timing(recorder.delay(), recorder.pin(), false, timing);
+    morse.output_str(text).unwrap();
+    recorder.timeline().iter().map(|(_, d)| d).sum()
+}
+
+#[test]
+fn dot_length_from_wpm() {
+    assert_eq!(Timing::from_wpm(20, ReferenceWord::Paris).dot(), 60);
+    assert_eq!(Timing::from_wpm(20, ReferenceWord::Codex).dot(), 50);
+    assert_eq!(Timing::from_wpm(4, ReferenceWord::Paris).dot(), 300);
+    // 1200 / 7 = 171.4
+    assert_eq!(Timing::from_wpm(7, ReferenceWord::Paris).dot(), 171);
+}
+
+#[test]
+fn wpm_round_trip() {
+    for reference in [ReferenceWord::Paris, ReferenceWord::Codex] {
+        // Above that, the ms resolution of the dot length gets too coarse
+        for wpm in 1..=30 {
+            assert_eq!(
+                Timing::from_wpm(wpm, reference).wpm(reference),
+                wpm,
+                "{} wpm {:?}",
+                wpm,
+                reference
+            );
+        }
+    }
+}
+
+#[test]
+fn wpm_matches_reference_word() {
+    for (reference, word) in [
+        (ReferenceWord::Paris, "PARIS"),
+        (ReferenceWord::Codex, "CODEX"),
+    ] {
+        let timing = Timing::from_dot_length(10);
+        let text = format!("{} {}", word, word);
+        let one_word = duration(&text, timing) - duration(word, timing);
+        assert_eq!(one_word, reference.units() * 10);
+        assert_eq!(u32::from(timing.wpm(reference)), 60_000 / one_word);
+    }
+}
+
+#[test]
+fn morse_wpm() {
+    let recorder = Recorder::new();
+    let mut morse = Morse::new_default(recorder.delay(), recorder.pin(), false);
+    assert_eq!(morse.wpm(ReferenceWord::Paris), 4);
+
+    morse.set_wpm(12, ReferenceWord::Codex);
+    assert_eq!(morse.timing().dot(), 83);
+    assert_eq!(morse.wpm(ReferenceWord::Codex), 12);
+
+    let morse = Morse::new_wpm(
+        recorder.delay(),
+        recorder.pin(),
+        false,
+        25,
+        ReferenceWord::Paris,
+    );
+    assert_eq!(morse.timing(), Timing::from_dot_length(48));
+}
+
+#[test]
+fn farnsworth() {
+    let timing = Timing::farnsworth(18, 5, ReferenceWord::Paris);
+    // Characters at 18 wpm
+    assert_eq!(timing.dot(), 67);
+    assert_eq!(timing.dash(), 201);
+    assert_eq!(timing.element_gap(), 67);
+    // 12000 ms per word, minus 31 * 67 ms for the characters, in 19 units
+    assert_eq!(timing.char_gap(), 1567);
+    assert_eq!(timing.word_gap(), 3656);
+    assert_eq!(timing.wpm(ReferenceWord::Paris), 5);
+
+    for reference in [ReferenceWord::Paris, ReferenceWord::Codex] {
+        for effective in 1..18 {
+            let timing = Timing::farnsworth(18, effective, reference);
+            assert_eq!(timing.wpm(reference), effective, "{} wpm", effective);
+        }
+    }
+}
+
+#[test]
+fn farnsworth_without_stretching() {
+    for effective in [20, 25] {
+        assert_eq!(
+            Timing::farnsworth(20, effective, ReferenceWord::Paris),
+            Timing::from_wpm(20, ReferenceWord::Paris)
+        );
+    }
+}
+
+#[test]
+fn morse_farnsworth() {
+    let recorder = Recorder::new();
+    let mut morse = Morse::new_default(recorder.delay(), recorder.pin(), false);
+    morse.set_farnsworth(15, 8, ReferenceWord::Codex);
+    assert_eq!(morse.wpm(ReferenceWord::Codex), 8);
+    assert_eq!(morse.timing().dot(), 67);
+    morse.output_str("E E").unwrap();
+    let gap = morse.timing().word_gap();
+    assert_eq!(
+        recorder.timeline(),
+        vec![(true, 67), (false, u32::from(gap)), (true, 67)]
+    );
+}
+
+#[test]
+fn weighting() {
+    let timing = Timing::from_dot_length(50).with_weighting(Weighting {
+        weight: 60,
+        dash_ratio: 35,
+    });
+    assert_eq!(timing.dot(), 60);
+    assert_eq!(timing.dash(), 185);
+    assert_eq!(timing.element_gap(), 40);
+    assert_eq!(timing.char_gap(), 140);
+    assert_eq!(timing.word_gap(), 340);
+
+    let light = Timing::from_dot_length(50).with_weighting(Weighting {
+        weight: 40,
+        ..Weighting::default()
+    });
+    assert_eq!((light.dot(), light.dash()), (40, 140));
+    assert_eq!(light.element_gap(), 60);
+    // Weight alone doesn't change the speed
+    assert_eq!(light.wpm(ReferenceWord::Paris), 24);
+}
+
+#[test]
+fn extreme_weighting_saturates() {
+    let timing = Timing::from_dot_length(10).with_weighting(Weighting {
+        weight: 255,
+        dash_ratio: 30,
+    });
+    assert_eq!(timing.element_gap(), 0);
+    assert_eq!(timing.dot(), 51);
+}
+
+#[test]
+fn long_dots_saturate() {
+    let timing = Timing::from_dot_length(10_000);
+    assert_eq!(timing.dot(), 10_000);
+    assert_eq!(timing.dash(), 30_000);
+    assert_eq!(timing.char_gap(), 30_000);
+    assert_eq!(timing.word_gap(), u16::MAX);
+    assert_eq!(Timing::from_dot_length(u16::MAX).dash(), u16::MAX);
+}
+
+#[test]
+fn morse_weighting() {
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), false, 20);
+    let weighting = Weighting {
+        weight: 75,
+        dash_ratio: 40,
+    };
+    morse.set_weighting(weighting);
+    // Changing the speed keeps the weighting
+    morse.set_wpm(12, ReferenceWord::Paris);
+    assert_eq!(morse.timing().weighting(), weighting);
+    morse.set_farnsworth(20, 10, ReferenceWord::Paris);
+    assert_eq!(morse.timing().weighting(), weighting);
+
+    morse.set_timing(Timing::from_dot_length(20).with_weighting(weighting));
+    morse.output_str("A E").unwrap();
+    assert_eq!(
+        recorder.timeline(),
+        vec![
+            (true, 30),
+            (false, 10),
+            (true, 90),
+            (false, 130),
+            (true, 30)
+        ]
+    );
+}
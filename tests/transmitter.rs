@@ -0,0 +1,129 @@
+mod common;
+
+use common::{blocking_timeline, Recorder, MESSAGES, TIMING};
+use embedded_morse::{encode, MorseTransmitter, PinSink, Prosign, Timing, Unsupported};
+
+const DOT: u16 = 10;
+
+#[test]
+fn poll_matches_blocking() {
+    for message in MESSAGES {
+        let recorder = Recorder::new();
+        let timing = Timing::from_dot_length(DOT);
+        let mut transmitter = MorseTransmitter::new(recorder.pin(), false, timing, message);
+        while let Some(duration) = transmitter.poll().unwrap() {
+            assert!(!transmitter.is_finished());
+            recorder.wait(duration);
+        }
+        assert!(transmitter.is_finished());
+        assert_eq!(
+            recorder.timeline(),
+            blocking_timeline(message, TIMING, Unsupported::Skip),
+            "{:?}",
+            message
+        );
+    }
+}
+
+#[test]
+fn tick_matches_blocking() {
+    for message in MESSAGES {
+        let recorder = Recorder::new();
+        let timing = Timing::from_dot_length(DOT);
+        let mut transmitter = MorseTransmitter::new(recorder.pin(), false, timing, message);
+        let mut elapsed = 0;
+        while transmitter.tick(elapsed).unwrap() {
+            elapsed = 2;
+            recorder.wait(elapsed);
+        }
+        assert!(!transmitter.tick(2).unwrap());
+        assert_eq!(
+            recorder.timeline(),
+            blocking_timeline(message, TIMING, Unsupported::Skip),
+            "{:?}",
+            message
+        );
+    }
+}
+
+/// Times of all edges of a timeline
+fn edges(timeline: &[(bool, u32)]) -> Vec<u32> {
+    timeline
+        .iter()
+        .scan(0, |time, (_, duration)| {
+            *time += duration;
+            Some(*time)
+        })
+        .collect()
+}
+
+#[test]
+fn tick_does_not_drift() {
+    for tick in [3, 7] {
+        let recorder = Recorder::new();
+        let timing = Timing::from_dot_length(DOT);
+        let mut transmitter = MorseTransmitter::new(recorder.pin(), false, timing, "PARIS PARIS");
+        let mut elapsed = 0;
+        while transmitter.tick(elapsed).unwrap() {
+            elapsed = tick;
+            recorder.wait(elapsed);
+        }
+        let expected = edges(&blocking_timeline("PARIS PARIS", TIMING, Unsupported::Skip));
+        let edges = edges(&recorder.timeline());
+        assert_eq!(edges.len(), expected.len(), "{} ms ticks", tick);
+        for (edge, expected) in edges.iter().zip(&expected) {
+            assert!(
+                (*expected..*expected + u32::from(tick)).contains(edge),
+                "{} ms ticks: edge at {} instead of {}",
+                tick,
+                edge,
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn ends_inactive() {
+    let recorder = Recorder::new();
+    let timing = Timing::from_dot_length(DOT);
+    let mut transmitter = MorseTransmitter::new(recorder.pin(), true, timing, "T");
+    assert_eq!(transmitter.poll().unwrap(), Some(DOT * 3));
+    assert_eq!(transmitter.poll().unwrap(), None);
+    assert_eq!(
+        recorder.events(),
+        vec![common::Event::Low, common::Event::High]
+    );
+    transmitter.free();
+}
+
+#[test]
+fn prosign() {
+    let recorder = Recorder::new();
+    let timing = Timing::from_dot_length(DOT);
+    let mut transmitter = MorseTransmitter::new_prosign(recorder.pin(), false, timing, Prosign::Sk);
+    while let Some(duration) = transmitter.poll().unwrap() {
+        recorder.wait(duration);
+    }
+    assert_eq!(
+        recorder.timeline(),
+        common::run_timeline("...-.-", u32::from(DOT))
+    );
+}
+
+#[test]
+fn elements() {
+    static TABLE: [(char, &str); 1] = [('#', "...-.-")];
+    let recorder = Recorder::new();
+    let timing = Timing::from_dot_length(DOT);
+    let sink = PinSink::new(recorder.pin(), false);
+    let elements = encode("# E").with_table(&TABLE);
+    let mut transmitter = MorseTransmitter::with_elements(sink, timing, elements);
+    while let Some(duration) = transmitter.poll().unwrap() {
+        recorder.wait(duration);
+    }
+    assert_eq!(
+        recorder.timeline(),
+        blocking_timeline("<SK> E", TIMING, Unsupported::Skip)
+    );
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{blocking_timeline, Event, Recorder, TIMING};
+use embedded_morse::{encode, find_unsupported, Error, Morse, Notation, Prosign, Unsupported};
+
+const DOT: u16 = 10;
+
+fn render(message: &str, policy: Unsupported) -> String {
+    let elements = encode(message).with_unsupported(policy);
+    Notation::STANDARD.render_elements(elements).to_string()
+}
+
+#[test]
+fn find() {
+    assert_eq!(find_unsupported("CQ de DL1ABC <AR> <a r>"), None);
+    assert_eq!(find_unsupported("E # E"), Some((2, '#')));
+    assert_eq!(find_unsupported("Ä"), Some((0, 'Ä')));
+    assert_eq!(find_unsupported("E\u{2003}E%"), Some((5, '%')));
+    assert_eq!(find_unsupported("E <AR"), Some((2, '<')));
+    assert_eq!(find_unsupported("E>"), Some((1, '>')));
+    assert_eq!(find_unsupported("<A<R>"), Some((2, '<')));
+}
+
+#[test]
+fn skip_is_default() {
+    assert_eq!(Unsupported::default(), Unsupported::Skip);
+    assert_eq!(
+        blocking_timeline("E # E", TIMING, Unsupported::Skip),
+        blocking_timeline("E E", TIMING, Unsupported::Skip)
+    );
+}
+
+#[test]
+fn substitute() {
+    assert_eq!(render("E#E", Unsupported::Substitute('?')), ". ..--.. .");
+    assert_eq!(
+        render("E # E", Unsupported::Substitute('?')),
+        ". / ..--.. / ."
+    );
+    assert_eq!(render("E#E", Unsupported::Substitute('%')), ". .");
+    assert_eq!(
+        render("E%<A#>", Unsupported::SubstituteProsign(Prosign::Error)),
+        ". ........ .-........"
+    );
+    assert_eq!(
+        blocking_timeline("E#", TIMING, Unsupported::Substitute('?')),
+        blocking_timeline("E?", TIMING, Unsupported::Skip)
+    );
+}
+
+#[test]
+fn fail_before_sending() {
+    let recorder = Recorder::new();
+    let mut morse = Morse::new(recorder.delay(), recorder.pin(), false, DOT);
+    morse.set_unsupported(Unsupported::Fail);
+    assert_eq!(
+        morse.output_str("SOS SOS #"),
+        Err(Error::Unsupported {
+            index: 8,
+            character: '#'
+        })
+    );
+    assert_eq!(recorder.events(), vec![]);
+    morse.output_str("E").unwrap();
+    assert_eq!(
+        recorder.events(),
+        vec![Event::High, Event::Delay(DOT), Event::Low]
+    );
+}
+
+#[test]
+fn display() {
+    let error: Error<&str> = Error::Unsupported {
+        index: 3,
+        character: '#',
+    };
+    assert_eq!(error.to_string(), "unsupported character '#' at index 3");
+    assert_eq!(Error::Output("pin").to_string(), "output error: pin");
+}